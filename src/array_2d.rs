@@ -0,0 +1,221 @@
+use std::iter::StepBy;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use arrayvec::Array;
+
+use super::array_shape::ArrayShape;
+
+/// A two-dimensional grid stored in row-major order.
+///
+/// The grid is parameterized by an array of rows, such as `[[T; 3]; 2]` for
+/// two rows of three columns.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Array2D<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    rows: A,
+}
+
+impl<T, B: ArrayShape<Item = T>, A: ArrayShape<Item = B>> Array2D<A> {
+    pub fn filled(x: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(|_, _| x.clone())
+    }
+
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(mut f: F) -> Self {
+        Array2D {
+            rows: A::from_fn(|r| B::from_fn(|c| f(r, c))),
+        }
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> Option<&T> {
+        if r < self.row_count() && c < self.column_count() {
+            Some(&self.row(r)[c])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, r: usize, c: usize) -> Option<&mut T> {
+        if r < self.row_count() && c < self.column_count() {
+            Some(&mut self.row_mut(r)[c])
+        } else {
+            None
+        }
+    }
+
+    /// Panics if the row is out of range.
+    pub fn row(&self, r: usize) -> &[T] {
+        assert!(r < self.row_count(), "row out of range");
+        let n = self.column_count();
+        &self.as_slice()[r * n..][..n]
+    }
+
+    /// Panics if the row is out of range.
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        assert!(r < self.row_count(), "row out of range");
+        let n = self.column_count();
+        &mut self.as_mut_slice()[r * n..][..n]
+    }
+
+    /// Panics if the column is out of range.
+    pub fn column(&self, c: usize) -> StepBy<slice::Iter<'_, T>> {
+        let n = self.column_count();
+        assert!(c < n, "column out of range");
+        self.as_slice()[c..].iter().step_by(n)
+    }
+
+    /// Panics if the column is out of range.
+    pub fn column_mut(&mut self, c: usize) -> StepBy<slice::IterMut<'_, T>> {
+        let n = self.column_count();
+        assert!(c < n, "column out of range");
+        self.as_mut_slice()[c..].iter_mut().step_by(n)
+    }
+
+    pub fn rows(&self) -> slice::Iter<'_, B> {
+        self.rows.as_ref().iter()
+    }
+
+    pub fn rows_mut(&mut self) -> slice::IterMut<'_, B> {
+        self.rows.as_mut().iter_mut()
+    }
+
+    pub fn fill(&mut self, x: &T)
+    where
+        T: Clone,
+    {
+        for y in self.as_mut_slice() {
+            *y = x.clone();
+        }
+    }
+
+    /// Panics if either of the positions is out of range.
+    pub fn swap(&mut self, (r, c): (usize, usize), (s, d): (usize, usize)) {
+        let n = self.column_count();
+        assert!(c < n && d < n, "column out of range");
+        self.as_mut_slice().swap(r * n + c, s * n + d);
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        let n = self.row_count() * self.column_count();
+        unsafe { slice::from_raw_parts(self.rows.as_ref().as_ptr() as *const T, n) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let n = self.row_count() * self.column_count();
+        unsafe { slice::from_raw_parts_mut(self.rows.as_mut().as_mut_ptr() as *mut T, n) }
+    }
+
+    pub fn row_count(&self) -> usize {
+        A::capacity()
+    }
+
+    pub fn column_count(&self) -> usize {
+        B::capacity()
+    }
+}
+
+impl<T: Default, B: ArrayShape<Item = T>, A: ArrayShape<Item = B>> Default for Array2D<A> {
+    fn default() -> Self {
+        Self::from_fn(|_, _| T::default())
+    }
+}
+
+impl<T, B: ArrayShape<Item = T>, A: ArrayShape<Item = B>> From<A> for Array2D<A> {
+    fn from(rows: A) -> Self {
+        Array2D { rows }
+    }
+}
+
+/// Panics if the position is out of range.
+impl<T, B: ArrayShape<Item = T>, A: ArrayShape<Item = B>> Index<(usize, usize)> for Array2D<A> {
+    type Output = T;
+
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        &self.row(r)[c]
+    }
+}
+
+/// Panics if the position is out of range.
+impl<T, B: ArrayShape<Item = T>, A: ArrayShape<Item = B>> IndexMut<(usize, usize)> for Array2D<A> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        &mut self.row_mut(r)[c]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        Array2D::<[[usize; 0]; 0]>::default();
+        Array2D::<[[usize; 3]; 2]>::filled(0);
+    }
+
+    #[test]
+    fn get() {
+        let mut g = Array2D::from([[0, 1, 2], [10, 11, 12]]);
+
+        assert_eq!(g.get(1, 2), Some(&12));
+        assert_eq!(g.get(2, 0), None);
+        assert_eq!(g.get(0, 3), None);
+        assert_eq!(g[(0, 1)], 1);
+
+        g[(0, 1)] = 42;
+        *g.get_mut(1, 0).unwrap() = 43;
+
+        assert_eq!(g.as_slice(), &[0, 42, 2, 43, 11, 12]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_range() {
+        let g = Array2D::from([[0, 1, 2], [10, 11, 12]]);
+
+        let _ = g[(0, 3)];
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut g = Array2D::from([[0, 1, 2], [10, 11, 12]]);
+
+        assert_eq!(g.row(1), &[10, 11, 12]);
+        assert_eq!(g.column(1).cloned().collect::<Vec<_>>(), vec![1, 11]);
+        assert_eq!(g.rows().count(), g.row_count());
+
+        for x in g.column_mut(2) {
+            *x = 0;
+        }
+
+        for x in g.row_mut(0) {
+            *x += 1;
+        }
+
+        assert_eq!(g, Array2D::from([[1, 2, 1], [10, 11, 0]]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn column_out_of_range() {
+        let g = Array2D::from([[0, 1, 2], [10, 11, 12]]);
+
+        let _ = g.column(3);
+    }
+
+    #[test]
+    fn fill_and_swap() {
+        let mut g = Array2D::from([[0, 1, 2], [10, 11, 12]]);
+
+        g.swap((0, 0), (1, 2));
+
+        assert_eq!(g[(0, 0)], 12);
+        assert_eq!(g[(1, 2)], 0);
+
+        g.fill(&7);
+
+        assert_eq!(g, Array2D::filled(7));
+        assert_eq!(g.column_count(), 3);
+    }
+}
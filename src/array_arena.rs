@@ -0,0 +1,306 @@
+use std::fmt::{self, Debug, Formatter};
+use std::iter::{Enumerate, FusedIterator};
+use std::mem;
+use std::slice;
+
+use arrayvec::Array;
+
+use super::array_shape::ArrayShape;
+use super::error::CapacityError;
+
+/// A key of an element in an arena.
+///
+/// Keys of removed elements are never valid again even if their slots are
+/// reused.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ArrayArenaKey {
+    index: usize,
+    generation: u32,
+}
+
+enum Entry<T> {
+    Occupied(T),
+    // An index of the next vacant slot.
+    Vacant(usize),
+}
+
+struct Slot<T> {
+    generation: u32,
+    entry: Entry<T>,
+}
+
+/// A generational arena which gives out stable keys to its elements.
+pub struct ArrayArena<A: ArrayShape> {
+    slots: A::With<Slot<<A as Array>::Item>>,
+    // An index of the first vacant slot which is the capacity if all slots are
+    // full.
+    free: usize,
+    length: usize,
+}
+
+impl<T, A: ArrayShape<Item = T>> ArrayArena<A> {
+    pub fn new() -> Self {
+        ArrayArena {
+            slots: A::with_fn(|i| Slot {
+                generation: 0,
+                entry: Entry::Vacant(i + 1),
+            }),
+            free: 0,
+            length: 0,
+        }
+    }
+
+    pub fn insert(&mut self, x: T) -> Result<ArrayArenaKey, CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(x));
+        }
+
+        let i = self.free;
+        let slot = &mut self.slots.as_mut()[i];
+
+        self.free = match mem::replace(&mut slot.entry, Entry::Occupied(x)) {
+            Entry::Vacant(j) => j,
+            Entry::Occupied(_) => unreachable!(),
+        };
+        self.length += 1;
+
+        Ok(ArrayArenaKey {
+            index: i,
+            generation: slot.generation,
+        })
+    }
+
+    pub fn remove(&mut self, k: ArrayArenaKey) -> Option<T> {
+        self.get(k)?;
+
+        let slot = &mut self.slots.as_mut()[k.index];
+        slot.generation = slot.generation.wrapping_add(1);
+
+        let x = match mem::replace(&mut slot.entry, Entry::Vacant(self.free)) {
+            Entry::Occupied(x) => x,
+            Entry::Vacant(_) => unreachable!(),
+        };
+
+        self.free = k.index;
+        self.length -= 1;
+
+        Some(x)
+    }
+
+    pub fn get(&self, k: ArrayArenaKey) -> Option<&T> {
+        match self.slots.as_ref().get(k.index)? {
+            Slot {
+                generation,
+                entry: Entry::Occupied(x),
+            } if *generation == k.generation => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, k: ArrayArenaKey) -> Option<&mut T> {
+        match self.slots.as_mut().get_mut(k.index)? {
+            Slot {
+                generation,
+                entry: Entry::Occupied(x),
+            } if *generation == k.generation => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, k: ArrayArenaKey) -> bool {
+        self.get(k).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        for i in 0..self.capacity() {
+            self.remove(ArrayArenaKey {
+                index: i,
+                generation: self.slots.as_ref()[i].generation,
+            });
+        }
+    }
+
+    pub fn iter(&self) -> ArrayArenaIterator<'_, T> {
+        ArrayArenaIterator {
+            slots: self.slots.as_ref().iter().enumerate(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> ArrayArenaMutIterator<'_, T> {
+        ArrayArenaMutIterator {
+            slots: self.slots.as_mut().iter_mut().enumerate(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+}
+
+impl<T: Debug, A: ArrayShape<Item = T>> Debug for ArrayArena<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<T, A: ArrayShape<Item = T>> Default for ArrayArena<A> {
+    fn default() -> Self {
+        ArrayArena::new()
+    }
+}
+
+impl<'a, T: 'a, A: ArrayShape<Item = T>> IntoIterator for &'a ArrayArena<A> {
+    type Item = (ArrayArenaKey, &'a T);
+    type IntoIter = ArrayArenaIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: 'a, A: ArrayShape<Item = T>> IntoIterator for &'a mut ArrayArena<A> {
+    type Item = (ArrayArenaKey, &'a mut T);
+    type IntoIter = ArrayArenaMutIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct ArrayArenaIterator<'a, T: 'a> {
+    slots: Enumerate<slice::Iter<'a, Slot<T>>>,
+}
+
+impl<'a, T> Iterator for ArrayArenaIterator<'a, T> {
+    type Item = (ArrayArenaKey, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in &mut self.slots {
+            if let Entry::Occupied(ref x) = slot.entry {
+                let generation = slot.generation;
+                return Some((ArrayArenaKey { index, generation }, x));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T> FusedIterator for ArrayArenaIterator<'a, T> {}
+
+pub struct ArrayArenaMutIterator<'a, T: 'a> {
+    slots: Enumerate<slice::IterMut<'a, Slot<T>>>,
+}
+
+impl<'a, T> Iterator for ArrayArenaMutIterator<'a, T> {
+    type Item = (ArrayArenaKey, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in &mut self.slots {
+            if let Entry::Occupied(ref mut x) = slot.entry {
+                let generation = slot.generation;
+                return Some((ArrayArenaKey { index, generation }, x));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T> FusedIterator for ArrayArenaMutIterator<'a, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArrayArena::<[usize; 0]>::new();
+        ArrayArena::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn insert() {
+        let mut a: ArrayArena<[usize; 2]> = ArrayArena::new();
+
+        let k = a.insert(1).unwrap();
+        let l = a.insert(2).unwrap();
+
+        assert_ne!(k, l);
+        assert_eq!(a.insert(3), Err(CapacityError::new(3)));
+        assert_eq!(a.get(k), Some(&1));
+        assert_eq!(a.get(l), Some(&2));
+        assert!(a.is_full());
+    }
+
+    #[test]
+    fn insert_into_zero_capacity() {
+        let mut a: ArrayArena<[usize; 0]> = ArrayArena::new();
+
+        assert_eq!(a.insert(1), Err(CapacityError::new(1)));
+    }
+
+    #[test]
+    fn remove() {
+        let mut a: ArrayArena<[usize; 2]> = ArrayArena::new();
+
+        let k = a.insert(1).unwrap();
+
+        assert_eq!(a.remove(k), Some(1));
+        assert_eq!(a.remove(k), None);
+        assert!(a.is_empty());
+
+        let l = a.insert(2).unwrap();
+
+        assert!(!a.contains(k));
+        assert_eq!(a.get(k), None);
+        assert_eq!(a.get_mut(k), None);
+        assert_eq!(a.get(l), Some(&2));
+    }
+
+    #[test]
+    fn iter() {
+        let mut a: ArrayArena<[usize; 4]> = ArrayArena::new();
+
+        let ks = (0..4).map(|x| a.insert(x).unwrap()).collect::<Vec<_>>();
+
+        a.remove(ks[1]);
+
+        for (_, x) in &mut a {
+            *x *= 10;
+        }
+
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            vec![(ks[0], &0), (ks[2], &20), (ks[3], &30)]
+        );
+    }
+
+    #[test]
+    fn clear() {
+        let mut a: ArrayArena<[Box<usize>; 4]> = ArrayArena::new();
+
+        let k = a.insert(Box::new(1)).unwrap();
+        a.insert(Box::new(2)).unwrap();
+        a.clear();
+
+        assert!(a.is_empty());
+        assert!(!a.contains(k));
+
+        for x in 0..4 {
+            assert!(a.insert(Box::new(x)).is_ok());
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt::{self, Debug, Formatter};
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+/// A max-heap. Wrap elements in `std::cmp::Reverse` for a min-heap.
+pub struct ArrayBinaryHeap<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    elements: ArrayQueue<A>,
+}
+
+impl<T: Ord, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> ArrayBinaryHeap<A> {
+    pub fn new() -> Self {
+        ArrayBinaryHeap {
+            elements: ArrayQueue::new(),
+        }
+    }
+
+    pub fn push(&mut self, x: T) -> Result<(), CapacityError<T>> {
+        self.elements.push_back(x)?;
+
+        let xs = self.elements.make_contiguous();
+        let mut i = xs.len() - 1;
+
+        while i > 0 {
+            let j = (i - 1) / 2;
+
+            if xs[i] <= xs[j] {
+                break;
+            }
+
+            xs.swap(i, j);
+            i = j;
+        }
+
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let l = self.len();
+        self.elements.swap(0, l - 1);
+        let x = self.elements.pop_back();
+
+        let xs = self.elements.make_contiguous();
+        let mut i = 0;
+
+        loop {
+            let mut j = i;
+
+            for k in &[2 * i + 1, 2 * i + 2] {
+                if *k < xs.len() && xs[*k] > xs[j] {
+                    j = *k;
+                }
+            }
+
+            if j == i {
+                break;
+            }
+
+            xs.swap(i, j);
+            i = j;
+        }
+
+        x
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut xs = Vec::with_capacity(self.len());
+
+        while let Some(x) = self.pop() {
+            xs.push(x);
+        }
+
+        xs.reverse();
+        xs
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.elements.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+}
+
+impl<T: Clone, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Clone for ArrayBinaryHeap<A> {
+    fn clone(&self) -> Self {
+        ArrayBinaryHeap {
+            elements: self.elements.clone(),
+        }
+    }
+}
+
+impl<T: Debug, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Debug for ArrayBinaryHeap<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.elements, f)
+    }
+}
+
+impl<T: Ord, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Default for ArrayBinaryHeap<A> {
+    fn default() -> Self {
+        ArrayBinaryHeap::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Reverse;
+
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArrayBinaryHeap::<[usize; 0]>::new();
+        ArrayBinaryHeap::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push() {
+        let mut h: ArrayBinaryHeap<[usize; 2]> = ArrayBinaryHeap::new();
+
+        assert_eq!(h.peek(), None);
+        assert!(h.push(1).is_ok());
+        assert_eq!(h.peek(), Some(&1));
+        assert!(h.push(2).is_ok());
+        assert_eq!(h.peek(), Some(&2));
+        assert_eq!(h.push(3), Err(CapacityError::new(3)));
+        assert_eq!(h.len(), 2);
+    }
+
+    #[test]
+    fn pop() {
+        let mut h: ArrayBinaryHeap<[usize; 16]> = ArrayBinaryHeap::new();
+
+        for &x in &[5, 3, 8, 1, 9, 2, 7, 3, 0, 6] {
+            assert!(h.push(x).is_ok());
+        }
+
+        for &x in &[9, 8, 7, 6, 5, 3, 3, 2, 1, 0] {
+            assert_eq!(h.pop(), Some(x));
+        }
+
+        assert_eq!(h.pop(), None);
+    }
+
+    #[test]
+    fn into_sorted_vec() {
+        let mut h: ArrayBinaryHeap<[usize; 8]> = ArrayBinaryHeap::new();
+
+        for &x in &[4, 1, 3, 2] {
+            assert!(h.push(x).is_ok());
+        }
+
+        assert_eq!(h.into_sorted_vec(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn min_heap() {
+        let mut h: ArrayBinaryHeap<[Reverse<usize>; 4]> = ArrayBinaryHeap::new();
+
+        for &x in &[2, 1, 3] {
+            assert!(h.push(Reverse(x)).is_ok());
+        }
+
+        assert_eq!(h.pop(), Some(Reverse(1)));
+        assert_eq!(h.pop(), Some(Reverse(2)));
+        assert_eq!(h.pop(), Some(Reverse(3)));
+    }
+}
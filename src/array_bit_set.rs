@@ -0,0 +1,313 @@
+use std::fmt::{self, Debug, Formatter};
+use std::iter::FusedIterator;
+use std::mem::MaybeUninit;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+
+use arrayvec::Array;
+
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// A set of bits stored in an array of words.
+pub struct ArrayBitSet<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> {
+    words: A,
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> ArrayBitSet<A> {
+    pub fn new() -> Self {
+        ArrayBitSet {
+            // All-zero words are valid integers.
+            words: unsafe { MaybeUninit::zeroed().assume_init() },
+        }
+    }
+
+    /// Sets a bit and returns its previous value.
+    ///
+    /// Panics if the index is out of range.
+    pub fn set(&mut self, i: usize) -> bool {
+        let x = self.test(i);
+        self.words.as_mut()[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+        x
+    }
+
+    /// Clears a bit and returns its previous value.
+    ///
+    /// Panics if the index is out of range.
+    pub fn clear(&mut self, i: usize) -> bool {
+        let x = self.test(i);
+        self.words.as_mut()[i / WORD_BITS] &= !(1 << (i % WORD_BITS));
+        x
+    }
+
+    /// Panics if the index is out of range.
+    pub fn test(&self, i: usize) -> bool {
+        assert!(i < self.capacity(), "index out of range");
+        self.words.as_ref()[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0
+    }
+
+    pub fn clear_all(&mut self) {
+        for w in self.words.as_mut() {
+            *w = 0;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .as_ref()
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn first_unset(&self) -> Option<usize> {
+        self.words
+            .as_ref()
+            .iter()
+            .enumerate()
+            .find(|(_, w)| **w != !0)
+            .map(|(i, w)| i * WORD_BITS + w.trailing_ones() as usize)
+    }
+
+    pub fn iter(&self) -> ArrayBitSetIterator<'_> {
+        ArrayBitSetIterator {
+            words: self.words.as_ref(),
+            index: 0,
+            word: self.words.as_ref().first().cloned().unwrap_or(0),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.as_ref().iter().all(|w| *w == 0)
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity() * WORD_BITS
+    }
+
+    fn zip_with<F: Fn(usize, usize) -> usize>(&mut self, other: &Self, f: F) {
+        for (x, y) in self.words.as_mut().iter_mut().zip(other.words.as_ref()) {
+            *x = f(*x, *y);
+        }
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> Clone for ArrayBitSet<A> {
+    fn clone(&self) -> Self {
+        let mut s = Self::new();
+        s.words.as_mut().copy_from_slice(self.words.as_ref());
+        s
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> Debug for ArrayBitSet<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_set().entries(self).finish()
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> Default for ArrayBitSet<A> {
+    fn default() -> Self {
+        ArrayBitSet::new()
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> PartialEq for ArrayBitSet<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.words.as_ref() == other.words.as_ref()
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> Eq for ArrayBitSet<A> {}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> BitAndAssign<&ArrayBitSet<A>>
+    for ArrayBitSet<A>
+{
+    fn bitand_assign(&mut self, other: &Self) {
+        self.zip_with(other, |x, y| x & y);
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> BitOrAssign<&ArrayBitSet<A>>
+    for ArrayBitSet<A>
+{
+    fn bitor_assign(&mut self, other: &Self) {
+        self.zip_with(other, |x, y| x | y);
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> BitXorAssign<&ArrayBitSet<A>>
+    for ArrayBitSet<A>
+{
+    fn bitxor_assign(&mut self, other: &Self) {
+        self.zip_with(other, |x, y| x ^ y);
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> BitAnd for &ArrayBitSet<A> {
+    type Output = ArrayBitSet<A>;
+
+    fn bitand(self, other: Self) -> ArrayBitSet<A> {
+        let mut s = self.clone();
+        s &= other;
+        s
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> BitOr for &ArrayBitSet<A> {
+    type Output = ArrayBitSet<A>;
+
+    fn bitor(self, other: Self) -> ArrayBitSet<A> {
+        let mut s = self.clone();
+        s |= other;
+        s
+    }
+}
+
+impl<A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> BitXor for &ArrayBitSet<A> {
+    type Output = ArrayBitSet<A>;
+
+    fn bitxor(self, other: Self) -> ArrayBitSet<A> {
+        let mut s = self.clone();
+        s ^= other;
+        s
+    }
+}
+
+impl<'a, A: Array<Item = usize> + AsRef<[usize]> + AsMut<[usize]>> IntoIterator
+    for &'a ArrayBitSet<A>
+{
+    type Item = usize;
+    type IntoIter = ArrayBitSetIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ArrayBitSetIterator<'a> {
+    words: &'a [usize],
+    index: usize,
+    // Bits of the current word which have not been yielded yet.
+    word: usize,
+}
+
+impl<'a> Iterator for ArrayBitSetIterator<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            self.index += 1;
+            self.word = *self.words.get(self.index)?;
+        }
+
+        let i = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+
+        Some(self.index * WORD_BITS + i)
+    }
+}
+
+impl<'a> FusedIterator for ArrayBitSetIterator<'a> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArrayBitSet::<[usize; 0]>::new();
+        ArrayBitSet::<[usize; 2]>::new();
+    }
+
+    #[test]
+    fn set_and_clear() {
+        let mut s: ArrayBitSet<[usize; 2]> = ArrayBitSet::new();
+
+        assert_eq!(s.capacity(), 2 * WORD_BITS);
+        assert!(s.is_empty());
+        assert!(!s.set(3));
+        assert!(s.set(3));
+        assert!(!s.set(WORD_BITS + 1));
+        assert!(s.test(3));
+        assert!(!s.test(4));
+        assert!(s.test(WORD_BITS + 1));
+        assert_eq!(s.count_ones(), 2);
+        assert!(s.clear(3));
+        assert!(!s.clear(3));
+        assert_eq!(s.count_ones(), 1);
+
+        s.clear_all();
+
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_range() {
+        let s: ArrayBitSet<[usize; 1]> = ArrayBitSet::new();
+
+        s.test(WORD_BITS);
+    }
+
+    #[test]
+    fn first_unset() {
+        let mut s: ArrayBitSet<[usize; 2]> = ArrayBitSet::new();
+
+        assert_eq!(s.first_unset(), Some(0));
+
+        for i in 0..WORD_BITS + 2 {
+            s.set(i);
+        }
+
+        assert_eq!(s.first_unset(), Some(WORD_BITS + 2));
+
+        s.clear(5);
+
+        assert_eq!(s.first_unset(), Some(5));
+
+        for i in 0..2 * WORD_BITS {
+            s.set(i);
+        }
+
+        assert_eq!(s.first_unset(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let mut s: ArrayBitSet<[usize; 3]> = ArrayBitSet::new();
+
+        for &i in &[0, 5, WORD_BITS - 1, 2 * WORD_BITS + 7] {
+            s.set(i);
+        }
+
+        assert_eq!(
+            s.iter().collect::<Vec<_>>(),
+            vec![0, 5, WORD_BITS - 1, 2 * WORD_BITS + 7]
+        );
+        assert_eq!(ArrayBitSet::<[usize; 0]>::new().iter().next(), None);
+    }
+
+    #[test]
+    fn bit_operations() {
+        let mut s: ArrayBitSet<[usize; 2]> = ArrayBitSet::new();
+        let mut t: ArrayBitSet<[usize; 2]> = ArrayBitSet::new();
+
+        s.set(1);
+        s.set(WORD_BITS);
+        t.set(1);
+        t.set(2);
+
+        assert_eq!((&s & &t).iter().collect::<Vec<_>>(), vec![1]);
+        assert_eq!((&s | &t).iter().collect::<Vec<_>>(), vec![1, 2, WORD_BITS]);
+        assert_eq!((&s ^ &t).iter().collect::<Vec<_>>(), vec![2, WORD_BITS]);
+
+        s ^= &t;
+
+        assert_eq!(format!("{:?}", s), format!("{{2, {}}}", WORD_BITS));
+
+        s |= &t;
+        s &= &t;
+
+        assert_eq!(s, t);
+    }
+}
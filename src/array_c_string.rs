@@ -0,0 +1,192 @@
+use std::ffi::CStr;
+use std::fmt::{self, Debug, Formatter};
+use std::os::raw::c_char;
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CStringError;
+
+pub struct ArrayCString<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> {
+    bytes: ArrayQueue<A>,
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> ArrayCString<A> {
+    pub fn new() -> Self {
+        let mut bytes = ArrayQueue::new();
+
+        assert!(bytes.push_back(0).is_ok(), "no room for nul terminator");
+
+        ArrayCString { bytes }
+    }
+
+    pub fn from_bytes_with_nul(bs: &[u8]) -> Result<Self, CStringError> {
+        match bs.split_last() {
+            Some((0, bs)) => {
+                let mut s = Self::new();
+                s.push_bytes(bs)?;
+                Ok(s)
+            }
+            _ => Err(CStringError::NotNulTerminated),
+        }
+    }
+
+    pub fn push(&mut self, b: u8) -> Result<(), CStringError> {
+        self.push_bytes(&[b])
+    }
+
+    pub fn push_bytes(&mut self, bs: &[u8]) -> Result<(), CStringError> {
+        if bs.contains(&0) {
+            return Err(CStringError::InteriorNul);
+        } else if bs.len() > self.bytes.remaining_capacity() {
+            return Err(CStringError::Capacity);
+        }
+
+        self.bytes.pop_back();
+        self.bytes.try_extend_from_slice(bs).unwrap();
+        self.bytes.push_back(0).unwrap();
+        Ok(())
+    }
+
+    pub fn push_str(&mut self, s: &str) -> Result<(), CStringError> {
+        self.push_bytes(s.as_bytes())
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.truncate_back(1);
+        *self.bytes.first_mut().unwrap() = 0;
+    }
+
+    pub fn as_ptr(&self) -> *const c_char {
+        self.bytes.as_ptr() as *const c_char
+    }
+
+    pub fn as_c_str(&self) -> &CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(self.as_bytes_with_nul()) }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        let bs = self.as_bytes_with_nul();
+        &bs[..bs.len() - 1]
+    }
+
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        self.bytes.as_contiguous_slice()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity() - 1
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Clone for ArrayCString<A> {
+    fn clone(&self) -> Self {
+        ArrayCString {
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Debug for ArrayCString<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(self.as_c_str(), f)
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Default for ArrayCString<A> {
+    fn default() -> Self {
+        ArrayCString::new()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> PartialEq for ArrayCString<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Eq for ArrayCString<A> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let s = ArrayCString::<[u8; 1]>::new();
+
+        assert!(s.is_empty());
+        assert_eq!(s.capacity(), 0);
+        assert_eq!(s.as_bytes_with_nul(), b"\0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_without_room_for_nul() {
+        ArrayCString::<[u8; 0]>::new();
+    }
+
+    #[test]
+    fn from_bytes_with_nul() {
+        let s = ArrayCString::<[u8; 4]>::from_bytes_with_nul(b"foo\0").unwrap();
+
+        assert_eq!(s.as_bytes(), b"foo");
+        assert_eq!(s.len(), 3);
+
+        assert_eq!(
+            ArrayCString::<[u8; 4]>::from_bytes_with_nul(b"foo"),
+            Err(CStringError::NotNulTerminated)
+        );
+        assert_eq!(
+            ArrayCString::<[u8; 4]>::from_bytes_with_nul(b"f\0o\0"),
+            Err(CStringError::InteriorNul)
+        );
+        assert_eq!(
+            ArrayCString::<[u8; 4]>::from_bytes_with_nul(b"fooo\0"),
+            Err(CStringError::Capacity)
+        );
+    }
+
+    #[test]
+    fn push() {
+        let mut s: ArrayCString<[u8; 3]> = ArrayCString::new();
+
+        assert!(s.push(b'a').is_ok());
+        assert_eq!(s.push(0), Err(CStringError::InteriorNul));
+        assert!(s.push_str("b").is_ok());
+        assert_eq!(s.push(b'c'), Err(CStringError::Capacity));
+        assert_eq!(s.as_bytes_with_nul(), b"ab\0");
+    }
+
+    #[test]
+    fn clear() {
+        let mut s: ArrayCString<[u8; 4]> = ArrayCString::new();
+
+        assert!(s.push_str("foo").is_ok());
+
+        s.clear();
+
+        assert!(s.is_empty());
+        assert_eq!(s.as_bytes_with_nul(), b"\0");
+    }
+
+    #[test]
+    fn as_ptr() {
+        let mut s: ArrayCString<[u8; 4]> = ArrayCString::new();
+
+        assert!(s.push_str("foo").is_ok());
+
+        let c = unsafe { CStr::from_ptr(s.as_ptr()) };
+
+        assert_eq!(c.to_bytes(), b"foo");
+        assert_eq!(s.as_c_str(), c);
+    }
+}
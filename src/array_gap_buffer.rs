@@ -0,0 +1,307 @@
+use std::fmt::{self, Debug, Formatter};
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::slice;
+
+use arrayvec::Array;
+
+use super::error::CapacityError;
+
+/// A gap buffer whose elements are inserted and deleted at a cursor.
+///
+/// Elements before and after the cursor are stored at the beginning and end
+/// of the backing array respectively, so that editing at the cursor takes O(1)
+/// time and moving it takes time proportional to the distance.
+pub struct ArrayGapBuffer<A: Array> {
+    array: MaybeUninit<A>,
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl<A: Array> ArrayGapBuffer<A> {
+    pub fn new() -> Self {
+        ArrayGapBuffer {
+            array: MaybeUninit::uninit(),
+            gap_start: 0,
+            gap_end: A::capacity(),
+        }
+    }
+
+    /// Inserts an element before the cursor.
+    pub fn insert(
+        &mut self,
+        x: <A as Array>::Item,
+    ) -> Result<(), CapacityError<<A as Array>::Item>> {
+        if self.is_full() {
+            return Err(CapacityError::new(x));
+        }
+
+        let i = self.gap_start;
+        unsafe { ptr::write(self.as_mut_ptr().add(i), x) };
+        self.gap_start += 1;
+
+        Ok(())
+    }
+
+    /// Deletes an element before the cursor.
+    pub fn delete_backward(&mut self) -> Option<<A as Array>::Item> {
+        if self.gap_start == 0 {
+            return None;
+        }
+
+        self.gap_start -= 1;
+        Some(unsafe { ptr::read(self.as_ptr().add(self.gap_start)) })
+    }
+
+    /// Deletes an element after the cursor.
+    pub fn delete_forward(&mut self) -> Option<<A as Array>::Item> {
+        if self.gap_end == self.capacity() {
+            return None;
+        }
+
+        self.gap_end += 1;
+        Some(unsafe { ptr::read(self.as_ptr().add(self.gap_end - 1)) })
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    pub fn move_left(&mut self) -> bool {
+        if self.gap_start == 0 {
+            return false;
+        }
+
+        self.gap_start -= 1;
+        self.gap_end -= 1;
+        self.move_element(self.gap_start, self.gap_end);
+
+        true
+    }
+
+    pub fn move_right(&mut self) -> bool {
+        if self.gap_end == self.capacity() {
+            return false;
+        }
+
+        self.move_element(self.gap_end, self.gap_start);
+        self.gap_start += 1;
+        self.gap_end += 1;
+
+        true
+    }
+
+    /// Panics if the position is out of range.
+    pub fn set_cursor(&mut self, i: usize) {
+        assert!(i <= self.len(), "cursor out of range");
+
+        while self.cursor() > i {
+            self.move_left();
+        }
+
+        while self.cursor() < i {
+            self.move_right();
+        }
+    }
+
+    /// Returns elements before and after the cursor.
+    pub fn as_slices(&self) -> (&[<A as Array>::Item], &[<A as Array>::Item]) {
+        unsafe {
+            (
+                slice::from_raw_parts(self.as_ptr(), self.gap_start),
+                slice::from_raw_parts(
+                    self.as_ptr().add(self.gap_end),
+                    self.capacity() - self.gap_end,
+                ),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [<A as Array>::Item], &mut [<A as Array>::Item]) {
+        let (i, j, n) = (self.gap_start, self.gap_end, self.capacity());
+        let p = self.as_mut_ptr();
+
+        unsafe {
+            (
+                slice::from_raw_parts_mut(p, i),
+                slice::from_raw_parts_mut(p.add(j), n - j),
+            )
+        }
+    }
+
+    pub fn clear(&mut self) {
+        while self.delete_backward().is_some() {}
+        while self.delete_forward().is_some() {}
+    }
+
+    pub fn len(&self) -> usize {
+        self.capacity() - (self.gap_end - self.gap_start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.gap_start == self.gap_end
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    fn move_element(&mut self, from: usize, to: usize) {
+        let p = self.as_mut_ptr();
+        unsafe { ptr::copy_nonoverlapping(p.add(from), p.add(to), 1) };
+    }
+
+    fn as_ptr(&self) -> *const <A as Array>::Item {
+        self.array.as_ptr() as *const <A as Array>::Item
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut <A as Array>::Item {
+        self.array.as_mut_ptr() as *mut <A as Array>::Item
+    }
+}
+
+impl<A: Array> Clone for ArrayGapBuffer<A>
+where
+    <A as Array>::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut b = Self::new();
+        let (xs, ys) = self.as_slices();
+
+        for x in ys.iter().rev() {
+            b.insert(x.clone()).ok().unwrap();
+            b.move_left();
+        }
+
+        for x in xs {
+            b.insert(x.clone()).ok().unwrap();
+        }
+
+        b
+    }
+}
+
+impl<A: Array> Debug for ArrayGapBuffer<A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (xs, ys) = self.as_slices();
+        f.debug_list().entries(xs.iter().chain(ys)).finish()
+    }
+}
+
+impl<A: Array> Default for ArrayGapBuffer<A> {
+    fn default() -> Self {
+        ArrayGapBuffer::new()
+    }
+}
+
+impl<A: Array> Drop for ArrayGapBuffer<A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn contents<A: Array>(b: &ArrayGapBuffer<A>) -> Vec<<A as Array>::Item>
+    where
+        <A as Array>::Item: Clone,
+    {
+        let (xs, ys) = b.as_slices();
+        xs.iter().chain(ys).cloned().collect()
+    }
+
+    #[test]
+    fn new() {
+        ArrayGapBuffer::<[u8; 0]>::new();
+        ArrayGapBuffer::<[u8; 8]>::new();
+    }
+
+    #[test]
+    fn insert() {
+        let mut b: ArrayGapBuffer<[u8; 3]> = ArrayGapBuffer::new();
+
+        assert!(b.insert(b'a').is_ok());
+        assert!(b.insert(b'c').is_ok());
+        assert!(b.move_left());
+        assert!(b.insert(b'b').is_ok());
+        assert_eq!(b.insert(b'd'), Err(CapacityError::new(b'd')));
+        assert!(b.is_full());
+        assert_eq!(b.cursor(), 2);
+        assert_eq!(b.as_slices(), (&b"ab"[..], &b"c"[..]));
+    }
+
+    #[test]
+    fn delete() {
+        let mut b: ArrayGapBuffer<[u8; 8]> = ArrayGapBuffer::new();
+
+        for &x in b"abcd" {
+            assert!(b.insert(x).is_ok());
+        }
+
+        b.set_cursor(2);
+
+        assert_eq!(b.delete_backward(), Some(b'b'));
+        assert_eq!(b.delete_forward(), Some(b'c'));
+        assert_eq!(contents(&b), b"ad".to_vec());
+        assert_eq!(b.delete_forward(), Some(b'd'));
+        assert_eq!(b.delete_forward(), None);
+        assert_eq!(b.delete_backward(), Some(b'a'));
+        assert_eq!(b.delete_backward(), None);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn move_cursor() {
+        let mut b: ArrayGapBuffer<[u8; 8]> = ArrayGapBuffer::new();
+
+        assert!(!b.move_left());
+        assert!(!b.move_right());
+
+        for &x in b"abc" {
+            assert!(b.insert(x).is_ok());
+        }
+
+        b.set_cursor(0);
+
+        assert_eq!(b.as_slices(), (&b""[..], &b"abc"[..]));
+        assert!(b.move_right());
+        assert_eq!(b.as_slices(), (&b"a"[..], &b"bc"[..]));
+
+        b.set_cursor(3);
+
+        assert!(!b.move_right());
+        assert_eq!(b.len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_cursor_out_of_range() {
+        ArrayGapBuffer::<[u8; 8]>::new().set_cursor(1);
+    }
+
+    #[test]
+    fn clone_and_drop() {
+        let mut b: ArrayGapBuffer<[Box<usize>; 4]> = ArrayGapBuffer::new();
+
+        for x in 0..3 {
+            assert!(b.insert(Box::new(x)).is_ok());
+        }
+
+        b.set_cursor(1);
+
+        let c = b.clone();
+
+        assert_eq!(c.cursor(), 1);
+        assert_eq!(contents(&c), contents(&b));
+        assert_eq!(format!("{:?}", c), "[0, 1, 2]");
+    }
+}
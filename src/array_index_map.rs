@@ -0,0 +1,284 @@
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+
+use arrayvec::Array;
+
+use super::array_map::{ArrayMapIterator, ArrayMapMutIterator};
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+/// A map which iterates over its entries in insertion order and gives access
+/// to them by index.
+///
+/// Lookups by key scan the entries linearly, so the map is meant for small
+/// capacities. Lookups by index take constant time.
+pub struct ArrayIndexMap<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    entries: ArrayQueue<A>,
+}
+
+impl<K: Eq, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> ArrayIndexMap<A> {
+    pub fn new() -> Self {
+        ArrayIndexMap {
+            entries: ArrayQueue::new(),
+        }
+    }
+
+    /// Inserts an entry at the end of the map. If the key exists already, its
+    /// value is replaced and the entry keeps its index.
+    pub fn insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+        match self.get_index_of(&k) {
+            Some(i) => Ok(self
+                .entries
+                .element_mut(i)
+                .map(|e| mem::replace(&mut e.1, v))),
+            None => {
+                self.entries.push_back((k, v))?;
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn get<'a>(&'a self, k: &K) -> Option<&'a V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let i = self.get_index_of(k)?;
+        self.entries.element(i).map(|(_, v)| v)
+    }
+
+    pub fn get_mut<'a>(&'a mut self, k: &K) -> Option<&'a mut V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let i = self.get_index_of(k)?;
+        self.entries.element_mut(i).map(|(_, v)| v)
+    }
+
+    pub fn get_index<'a>(&'a self, i: usize) -> Option<(&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.entries.element(i).map(|(k, v)| (k, v))
+    }
+
+    pub fn get_index_mut<'a>(&'a mut self, i: usize) -> Option<(&'a K, &'a mut V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.entries.element_mut(i).map(|(k, v)| (&*k, v))
+    }
+
+    pub fn get_index_of(&self, k: &K) -> Option<usize> {
+        self.iter().position(|(l, _)| l == k)
+    }
+
+    /// Removes an entry by moving the last entry into its place.
+    pub fn swap_remove(&mut self, k: &K) -> Option<V> {
+        let i = self.get_index_of(k)?;
+        self.swap_remove_index(i).map(|(_, v)| v)
+    }
+
+    pub fn swap_remove_index(&mut self, i: usize) -> Option<(K, V)> {
+        self.entries.swap_remove_back(i)
+    }
+
+    /// Removes an entry by shifting all entries after it.
+    pub fn shift_remove(&mut self, k: &K) -> Option<V> {
+        let i = self.get_index_of(k)?;
+        self.shift_remove_index(i).map(|(_, v)| v)
+    }
+
+    pub fn shift_remove_index(&mut self, i: usize) -> Option<(K, V)> {
+        self.entries.remove(i)
+    }
+
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.entries.pop_back()
+    }
+
+    pub fn first<'a>(&'a self) -> Option<(&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.entries.first().map(|(k, v)| (k, v))
+    }
+
+    pub fn last<'a>(&'a self) -> Option<(&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.entries.last().map(|(k, v)| (k, v))
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.get_index_of(k).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter<'a>(&'a self) -> ArrayMapIterator<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.into_iter()
+    }
+
+    pub fn iter_mut<'a>(&'a mut self) -> ArrayMapMutIterator<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+}
+
+impl<K: Clone, V: Clone, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Clone
+    for ArrayIndexMap<A>
+{
+    fn clone(&self) -> Self {
+        ArrayIndexMap {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Debug
+    for ArrayIndexMap<A>
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<K: Eq, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Default
+    for ArrayIndexMap<A>
+{
+    fn default() -> Self {
+        ArrayIndexMap::new()
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> IntoIterator
+    for &'a ArrayIndexMap<A>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = ArrayMapIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayMapIterator::new(&self.entries)
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> IntoIterator
+    for &'a mut ArrayIndexMap<A>
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = ArrayMapMutIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayMapMutIterator::new(&mut self.entries)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArrayIndexMap::<[(usize, usize); 0]>::new();
+        ArrayIndexMap::<[(usize, usize); 8]>::new();
+    }
+
+    #[test]
+    fn insert() {
+        let mut m: ArrayIndexMap<[(usize, usize); 4]> = ArrayIndexMap::new();
+
+        for &k in &[3, 1, 2] {
+            assert_eq!(m.insert(k, 10 * k), Ok(None));
+        }
+
+        assert_eq!(m.insert(1, 0), Ok(Some(10)));
+        assert_eq!(m.insert(4, 40), Ok(None));
+        assert_eq!(m.insert(5, 50), Err(CapacityError::new((5, 50))));
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![(&3, &30), (&1, &0), (&2, &20), (&4, &40)]
+        );
+    }
+
+    #[test]
+    fn index() {
+        let mut m: ArrayIndexMap<[(usize, usize); 4]> = ArrayIndexMap::new();
+
+        for &k in &[3, 1, 2] {
+            assert_eq!(m.insert(k, 10 * k), Ok(None));
+        }
+
+        assert_eq!(m.get_index(1), Some((&1, &10)));
+        assert_eq!(m.get_index(3), None);
+        assert_eq!(m.get_index_of(&2), Some(2));
+        assert_eq!(m.get_index_of(&4), None);
+        assert_eq!(m.first(), Some((&3, &30)));
+        assert_eq!(m.last(), Some((&2, &20)));
+
+        *m.get_index_mut(0).unwrap().1 += 1;
+
+        assert_eq!(m.get(&3), Some(&31));
+    }
+
+    #[test]
+    fn swap_remove() {
+        let mut m: ArrayIndexMap<[(usize, usize); 4]> = ArrayIndexMap::new();
+
+        for &k in &[1, 2, 3, 4] {
+            assert_eq!(m.insert(k, 10 * k), Ok(None));
+        }
+
+        assert_eq!(m.swap_remove(&2), Some(20));
+        assert_eq!(m.swap_remove(&2), None);
+        assert_eq!(m.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![1, 4, 3]);
+        assert_eq!(m.swap_remove_index(0), Some((1, 10)));
+        assert_eq!(m.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![3, 4]);
+    }
+
+    #[test]
+    fn shift_remove() {
+        let mut m: ArrayIndexMap<[(usize, usize); 4]> = ArrayIndexMap::new();
+
+        for &k in &[1, 2, 3, 4] {
+            assert_eq!(m.insert(k, 10 * k), Ok(None));
+        }
+
+        assert_eq!(m.shift_remove(&2), Some(20));
+        assert_eq!(m.shift_remove(&2), None);
+        assert_eq!(m.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![1, 3, 4]);
+        assert_eq!(m.pop(), Some((4, 40)));
+        assert_eq!(m.len(), 2);
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+
+use arrayvec::Array;
+
+use super::array_map::ArrayMapIterator;
+use super::array_queue::ArrayQueue;
+
+/// A least-recently-used cache.
+///
+/// Entries are kept in order of use from the least recent to the most recent
+/// one.
+pub struct ArrayLru<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    entries: ArrayQueue<A>,
+}
+
+impl<K: Eq, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> ArrayLru<A> {
+    pub fn new() -> Self {
+        ArrayLru {
+            entries: ArrayQueue::new(),
+        }
+    }
+
+    pub fn get<'a>(&'a mut self, k: &K) -> Option<&'a V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.get_mut(k).map(|v| &*v)
+    }
+
+    pub fn get_mut<'a>(&'a mut self, k: &K) -> Option<&'a mut V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let i = self.position(k)?;
+        self.touch(i);
+        self.entries.last_mut().map(|(_, v)| v)
+    }
+
+    /// Returns a value without marking its entry as recently used.
+    pub fn peek<'a>(&'a self, k: &K) -> Option<&'a V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.iter().find(|&(l, _)| l == k).map(|(_, v)| v)
+    }
+
+    /// Inserts an entry as the most recently used one. Returns an entry of the
+    /// same key or the least recently used entry if it is removed.
+    pub fn put(&mut self, k: K, v: V) -> Option<(K, V)> {
+        if let Some(i) = self.position(&k) {
+            self.touch(i);
+            let e = self.entries.last_mut().unwrap();
+            return Some(mem::replace(e, (k, v)));
+        }
+
+        if self.capacity() == 0 {
+            return Some((k, v));
+        }
+
+        let e = if self.is_full() {
+            self.entries.pop_front()
+        } else {
+            None
+        };
+
+        self.entries.push_back((k, v)).ok().unwrap();
+        e
+    }
+
+    pub fn remove(&mut self, k: &K) -> Option<V> {
+        let i = self.position(k)?;
+        self.entries.remove(i).map(|(_, v)| v)
+    }
+
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        self.entries.pop_front()
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.position(k).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter<'a>(&'a self) -> ArrayMapIterator<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    fn position(&self, k: &K) -> Option<usize> {
+        self.entries.into_iter().position(|(l, _)| l == k)
+    }
+
+    fn touch(&mut self, i: usize) {
+        let e = self.entries.remove(i).unwrap();
+        self.entries.push_back(e).ok().unwrap();
+    }
+}
+
+impl<K: Clone, V: Clone, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Clone
+    for ArrayLru<A>
+{
+    fn clone(&self) -> Self {
+        ArrayLru {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Debug
+    for ArrayLru<A>
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<K: Eq, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Default
+    for ArrayLru<A>
+{
+    fn default() -> Self {
+        ArrayLru::new()
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> IntoIterator
+    for &'a ArrayLru<A>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = ArrayMapIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayMapIterator::new(&self.entries)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArrayLru::<[(usize, usize); 0]>::new();
+        ArrayLru::<[(usize, usize); 8]>::new();
+    }
+
+    #[test]
+    fn put() {
+        let mut c: ArrayLru<[(usize, &str); 2]> = ArrayLru::new();
+
+        assert_eq!(c.put(1, "a"), None);
+        assert_eq!(c.put(2, "b"), None);
+        assert_eq!(c.put(3, "c"), Some((1, "a")));
+        assert_eq!(c.put(2, "d"), Some((2, "b")));
+        assert_eq!(c.put(4, "e"), Some((3, "c")));
+        assert_eq!(c.iter().collect::<Vec<_>>(), vec![(&2, &"d"), (&4, &"e")]);
+    }
+
+    #[test]
+    fn put_into_zero_capacity() {
+        let mut c: ArrayLru<[(usize, usize); 0]> = ArrayLru::new();
+
+        assert_eq!(c.put(1, 1), Some((1, 1)));
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn get() {
+        let mut c: ArrayLru<[(usize, usize); 2]> = ArrayLru::new();
+
+        c.put(1, 10);
+        c.put(2, 20);
+
+        assert_eq!(c.get(&1), Some(&10));
+        assert_eq!(c.get(&3), None);
+        assert_eq!(c.put(3, 30), Some((2, 20)));
+
+        *c.get_mut(&1).unwrap() += 1;
+
+        assert_eq!(c.put(4, 40), Some((3, 30)));
+        assert_eq!(c.peek(&1), Some(&11));
+    }
+
+    #[test]
+    fn peek() {
+        let mut c: ArrayLru<[(usize, usize); 2]> = ArrayLru::new();
+
+        c.put(1, 10);
+        c.put(2, 20);
+
+        assert_eq!(c.peek(&1), Some(&10));
+        assert_eq!(c.put(3, 30), Some((1, 10)));
+        assert!(!c.contains_key(&1));
+    }
+
+    #[test]
+    fn remove() {
+        let mut c: ArrayLru<[(usize, usize); 3]> = ArrayLru::new();
+
+        c.put(1, 10);
+        c.put(2, 20);
+        c.put(3, 30);
+
+        assert_eq!(c.remove(&2), Some(20));
+        assert_eq!(c.remove(&2), None);
+        assert_eq!(c.pop_lru(), Some((1, 10)));
+        assert_eq!(c.len(), 1);
+    }
+}
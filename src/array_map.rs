@@ -0,0 +1,426 @@
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+use std::slice;
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+pub struct ArrayMap<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    entries: ArrayQueue<A>,
+}
+
+impl<K: Eq, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> ArrayMap<A> {
+    pub fn new() -> Self {
+        ArrayMap {
+            entries: ArrayQueue::new(),
+        }
+    }
+
+    pub fn insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+        if let Some(x) = self.get_mut(&k) {
+            return Ok(Some(mem::replace(x, v)));
+        }
+
+        self.entries.push_back((k, v))?;
+        Ok(None)
+    }
+
+    pub fn entry<'a>(&'a mut self, k: K) -> Entry<'a, K, V, A>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        match self.position(&k) {
+            Some(i) => Entry::Occupied(OccupiedEntry {
+                value: self.iter_mut().nth(i).unwrap().1,
+                key: k,
+            }),
+            None => Entry::Vacant(VacantEntry { key: k, map: self }),
+        }
+    }
+
+    pub fn get<'a>(&'a self, k: &K) -> Option<&'a V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.iter().find(|&(l, _)| l == k).map(|(_, v)| v)
+    }
+
+    pub fn get_mut<'a>(&'a mut self, k: &K) -> Option<&'a mut V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.iter_mut().find(|&(l, _)| l == k).map(|(_, v)| v)
+    }
+
+    pub fn remove(&mut self, k: &K) -> Option<V> {
+        let i = self.position(k)?;
+        self.entries.swap_remove_back(i).map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.position(k).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter<'a>(&'a self) -> ArrayMapIterator<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.into_iter()
+    }
+
+    pub fn iter_mut<'a>(&'a mut self) -> ArrayMapMutIterator<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    fn position(&self, k: &K) -> Option<usize> {
+        self.iter().position(|(l, _)| l == k)
+    }
+}
+
+impl<K: Clone, V: Clone, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Clone
+    for ArrayMap<A>
+{
+    fn clone(&self) -> Self {
+        ArrayMap {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Debug
+    for ArrayMap<A>
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<K: Eq, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Default
+    for ArrayMap<A>
+{
+    fn default() -> Self {
+        ArrayMap::new()
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> IntoIterator
+    for &'a ArrayMap<A>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = ArrayMapIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayMapIterator::new(&self.entries)
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> IntoIterator
+    for &'a mut ArrayMap<A>
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = ArrayMapMutIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayMapMutIterator::new(&mut self.entries)
+    }
+}
+
+pub enum Entry<'a, K: 'a, V: 'a, A: 'a + Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V, A>),
+}
+
+impl<'a, K: Eq, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Entry<'a, K, V, A> {
+    pub fn or_insert(self, v: V) -> Result<&'a mut V, CapacityError<(K, V)>> {
+        match self {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => e.insert(v),
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(
+        self,
+        f: F,
+    ) -> Result<&'a mut V, CapacityError<(K, V)>> {
+        match self {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => e.insert(f()),
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(ref mut e) = self {
+            f(e.get_mut());
+        }
+
+        self
+    }
+
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => e.key(),
+        }
+    }
+}
+
+pub struct OccupiedEntry<'a, K, V: 'a> {
+    key: K,
+    value: &'a mut V,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn get(&self) -> &V {
+        self.value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.value
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        self.value
+    }
+
+    pub fn insert(&mut self, v: V) -> V {
+        mem::replace(self.value, v)
+    }
+}
+
+pub struct VacantEntry<
+    'a,
+    K: 'a,
+    V: 'a,
+    A: 'a + Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>,
+> {
+    key: K,
+    map: &'a mut ArrayMap<A>,
+}
+
+impl<'a, K: Eq, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>>
+    VacantEntry<'a, K, V, A>
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn insert(self, v: V) -> Result<&'a mut V, CapacityError<(K, V)>> {
+        self.map.entries.push_back((self.key, v))?;
+        Ok(&mut self.map.entries.last_mut().unwrap().1)
+    }
+}
+
+#[derive(Debug)]
+pub struct ArrayMapIterator<'a, K: 'a, V: 'a> {
+    first: slice::Iter<'a, (K, V)>,
+    second: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> ArrayMapIterator<'a, K, V> {
+    pub(crate) fn new<A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>>(
+        entries: &'a ArrayQueue<A>,
+    ) -> Self {
+        let (xs, ys) = entries.as_slices();
+
+        ArrayMapIterator {
+            first: xs.iter(),
+            second: ys.iter(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for ArrayMapIterator<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first
+            .next()
+            .or_else(|| self.second.next())
+            .map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.first.len() + self.second.len();
+        (n, Some(n))
+    }
+}
+
+#[derive(Debug)]
+pub struct ArrayMapMutIterator<'a, K: 'a, V: 'a> {
+    first: slice::IterMut<'a, (K, V)>,
+    second: slice::IterMut<'a, (K, V)>,
+}
+
+impl<'a, K, V> ArrayMapMutIterator<'a, K, V> {
+    pub(crate) fn new<A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>>(
+        entries: &'a mut ArrayQueue<A>,
+    ) -> Self {
+        let (xs, ys) = entries.as_mut_slices();
+
+        ArrayMapMutIterator {
+            first: xs.iter_mut(),
+            second: ys.iter_mut(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for ArrayMapMutIterator<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first
+            .next()
+            .or_else(|| self.second.next())
+            .map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.first.len() + self.second.len();
+        (n, Some(n))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArrayMap::<[(usize, usize); 0]>::new();
+        ArrayMap::<[(usize, usize); 4]>::new();
+    }
+
+    #[test]
+    fn insert() {
+        let mut m: ArrayMap<[(usize, &str); 2]> = ArrayMap::new();
+
+        assert_eq!(m.insert(1, "a"), Ok(None));
+        assert_eq!(m.insert(2, "b"), Ok(None));
+        assert_eq!(m.insert(1, "c"), Ok(Some("a")));
+        assert_eq!(m.insert(3, "d"), Err(CapacityError::new((3, "d"))));
+        assert_eq!(m.len(), 2);
+        assert!(m.is_full());
+    }
+
+    #[test]
+    fn get() {
+        let mut m: ArrayMap<[(usize, usize); 2]> = ArrayMap::new();
+
+        assert_eq!(m.get(&1), None);
+        assert!(m.insert(1, 10).is_ok());
+        assert_eq!(m.get(&1), Some(&10));
+
+        *m.get_mut(&1).unwrap() += 1;
+
+        assert_eq!(m.get(&1), Some(&11));
+        assert_eq!(m.get_mut(&2), None);
+    }
+
+    #[test]
+    fn entry() {
+        let mut m: ArrayMap<[(char, usize); 2]> = ArrayMap::new();
+
+        for c in "abba".chars() {
+            *m.entry(c).or_insert(0).unwrap() += 1;
+        }
+
+        assert_eq!(m.get(&'a'), Some(&2));
+        assert_eq!(m.get(&'b'), Some(&2));
+        assert_eq!(m.entry('c').key(), &'c');
+        assert_eq!(
+            m.entry('c').or_insert_with(|| 1),
+            Err(CapacityError::new(('c', 1)))
+        );
+    }
+
+    #[test]
+    fn entry_and_modify() {
+        let mut m: ArrayMap<[(char, usize); 2]> = ArrayMap::new();
+
+        assert_eq!(
+            m.entry('a').and_modify(|v| *v += 1).or_insert(42),
+            Ok(&mut 42)
+        );
+        assert_eq!(
+            m.entry('a').and_modify(|v| *v += 1).or_insert(42),
+            Ok(&mut 43)
+        );
+
+        match m.entry('a') {
+            Entry::Occupied(mut e) => assert_eq!(e.insert(0), 43),
+            Entry::Vacant(_) => unreachable!(),
+        }
+
+        assert_eq!(m.get(&'a'), Some(&0));
+    }
+
+    #[test]
+    fn remove() {
+        let mut m: ArrayMap<[(usize, usize); 4]> = ArrayMap::new();
+
+        for i in 0..4 {
+            assert!(m.insert(i, i * 10).is_ok());
+        }
+
+        assert_eq!(m.remove(&1), Some(10));
+        assert_eq!(m.remove(&1), None);
+        assert!(!m.contains_key(&1));
+        assert!(m.contains_key(&3));
+        assert_eq!(m.len(), 3);
+        assert_eq!(m.get(&3), Some(&30));
+    }
+
+    #[test]
+    fn iterate() {
+        let mut m: ArrayMap<[(usize, usize); 4]> = ArrayMap::new();
+
+        for i in 0..3 {
+            assert!(m.insert(i, i).is_ok());
+        }
+
+        for (_, v) in &mut m {
+            *v += 1;
+        }
+
+        for (k, v) in &m {
+            assert_eq!(*v, k + 1);
+        }
+
+        assert_eq!(m.iter().count(), 3);
+        assert_eq!(format!("{:?}", m), "{0: 1, 1: 2, 2: 3}");
+    }
+}
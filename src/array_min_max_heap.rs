@@ -0,0 +1,349 @@
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+pub struct ArrayMinMaxHeap<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    elements: ArrayQueue<A>,
+}
+
+impl<T: Ord, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> ArrayMinMaxHeap<A> {
+    pub fn new() -> Self {
+        ArrayMinMaxHeap {
+            elements: ArrayQueue::new(),
+        }
+    }
+
+    pub fn push(&mut self, x: T) -> Result<(), CapacityError<T>> {
+        self.elements.push_back(x)?;
+
+        let xs = self.elements.make_contiguous();
+        let i = xs.len() - 1;
+        bubble_up(xs, i);
+
+        Ok(())
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.remove(0))
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let i = self.max_index();
+        Some(self.remove(i))
+    }
+
+    pub fn push_pop_min(&mut self, x: T) -> T {
+        if self.peek_min().map(|y| x <= *y).unwrap_or(true) {
+            return x;
+        }
+
+        let xs = self.elements.make_contiguous();
+        let y = mem::replace(&mut xs[0], x);
+        trickle_down(xs, 0);
+        y
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(&self.elements.as_contiguous_slice()[self.max_index()])
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.elements.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+
+    fn max_index(&self) -> usize {
+        let xs = self.elements.as_contiguous_slice();
+
+        match xs.len() {
+            0 | 1 => 0,
+            2 => 1,
+            _ if xs[1] >= xs[2] => 1,
+            _ => 2,
+        }
+    }
+
+    fn remove(&mut self, i: usize) -> T {
+        let l = self.len();
+        self.elements.swap(i, l - 1);
+        let x = self.elements.pop_back().unwrap();
+
+        let xs = self.elements.make_contiguous();
+
+        if i < xs.len() {
+            trickle_down(xs, i);
+        }
+
+        x
+    }
+}
+
+impl<T: Clone, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Clone for ArrayMinMaxHeap<A> {
+    fn clone(&self) -> Self {
+        ArrayMinMaxHeap {
+            elements: self.elements.clone(),
+        }
+    }
+}
+
+impl<T: Debug, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Debug for ArrayMinMaxHeap<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.elements, f)
+    }
+}
+
+impl<T: Ord, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Default for ArrayMinMaxHeap<A> {
+    fn default() -> Self {
+        ArrayMinMaxHeap::new()
+    }
+}
+
+fn is_min_level(i: usize) -> bool {
+    (usize::BITS - (i + 1).leading_zeros()) % 2 == 1
+}
+
+fn parent(i: usize) -> usize {
+    (i - 1) / 2
+}
+
+fn better<T: Ord>(x: &T, y: &T, min: bool) -> bool {
+    if min {
+        x < y
+    } else {
+        x > y
+    }
+}
+
+fn bubble_up<T: Ord>(xs: &mut [T], i: usize) {
+    if i == 0 {
+        return;
+    }
+
+    let p = parent(i);
+    let min = is_min_level(i);
+
+    if better(&xs[p], &xs[i], min) {
+        xs.swap(i, p);
+        bubble_up_grandparents(xs, p, !min);
+    } else {
+        bubble_up_grandparents(xs, i, min);
+    }
+}
+
+fn bubble_up_grandparents<T: Ord>(xs: &mut [T], mut i: usize, min: bool) {
+    while i >= 3 {
+        let g = parent(parent(i));
+
+        if !better(&xs[i], &xs[g], min) {
+            break;
+        }
+
+        xs.swap(i, g);
+        i = g;
+    }
+}
+
+fn trickle_down<T: Ord>(xs: &mut [T], mut i: usize) {
+    let min = is_min_level(i);
+
+    loop {
+        let mut m = i;
+
+        for j in [
+            2 * i + 1,
+            2 * i + 2,
+            4 * i + 3,
+            4 * i + 4,
+            4 * i + 5,
+            4 * i + 6,
+        ]
+        .iter()
+        .cloned()
+        {
+            if j < xs.len() && (m == i || better(&xs[j], &xs[m], min)) {
+                m = j;
+            }
+        }
+
+        if m == i || !better(&xs[m], &xs[i], min) {
+            return;
+        }
+
+        xs.swap(m, i);
+
+        if m <= 2 * i + 2 {
+            return;
+        }
+
+        let p = parent(m);
+
+        if better(&xs[p], &xs[m], min) {
+            xs.swap(m, p);
+        }
+
+        i = m;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ELEMENTS: &[usize] = &[5, 3, 8, 1, 9, 2, 7, 3, 0, 6, 4, 8, 11, 10, 12, 1];
+
+    #[test]
+    fn new() {
+        ArrayMinMaxHeap::<[usize; 0]>::new();
+        ArrayMinMaxHeap::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push() {
+        let mut h: ArrayMinMaxHeap<[usize; 2]> = ArrayMinMaxHeap::new();
+
+        assert!(h.push(1).is_ok());
+        assert!(h.push(2).is_ok());
+        assert_eq!(h.push(3), Err(CapacityError::new(3)));
+        assert_eq!(h.peek_min(), Some(&1));
+        assert_eq!(h.peek_max(), Some(&2));
+    }
+
+    #[test]
+    fn pop_min() {
+        let mut h: ArrayMinMaxHeap<[usize; 32]> = ArrayMinMaxHeap::new();
+        let mut xs = ELEMENTS.to_vec();
+        xs.sort();
+
+        for &x in ELEMENTS {
+            assert!(h.push(x).is_ok());
+        }
+
+        for x in xs {
+            assert_eq!(h.pop_min(), Some(x));
+        }
+
+        assert_eq!(h.pop_min(), None);
+    }
+
+    #[test]
+    fn pop_max() {
+        let mut h: ArrayMinMaxHeap<[usize; 32]> = ArrayMinMaxHeap::new();
+        let mut xs = ELEMENTS.to_vec();
+        xs.sort();
+
+        for &x in ELEMENTS {
+            assert!(h.push(x).is_ok());
+        }
+
+        for x in xs.into_iter().rev() {
+            assert_eq!(h.pop_max(), Some(x));
+        }
+
+        assert_eq!(h.pop_max(), None);
+    }
+
+    #[test]
+    fn pop_min_and_max_alternately() {
+        let mut h: ArrayMinMaxHeap<[usize; 32]> = ArrayMinMaxHeap::new();
+        let mut xs = ELEMENTS.to_vec();
+        xs.sort();
+
+        for &x in ELEMENTS {
+            assert!(h.push(x).is_ok());
+        }
+
+        while !xs.is_empty() {
+            assert_eq!(h.pop_min(), Some(xs.remove(0)));
+            assert_eq!(h.pop_max(), xs.pop());
+        }
+
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn push_and_pop_randomly() {
+        let mut h: ArrayMinMaxHeap<[usize; 64]> = ArrayMinMaxHeap::new();
+        let mut xs = vec![];
+        let mut r: usize = 42;
+
+        for _ in 0..1000 {
+            r = r
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let x = (r >> 33) % 100;
+
+            match x % 5 {
+                0..=2 if !h.is_full() => {
+                    assert!(h.push(x).is_ok());
+                    xs.push(x);
+                    xs.sort();
+                }
+                1 => assert_eq!(
+                    h.pop_min(),
+                    if xs.is_empty() {
+                        None
+                    } else {
+                        Some(xs.remove(0))
+                    }
+                ),
+                _ => assert_eq!(h.pop_max(), xs.pop()),
+            }
+
+            assert_eq!(h.peek_min(), xs.first());
+            assert_eq!(h.peek_max(), xs.last());
+        }
+    }
+
+    #[test]
+    fn push_pop_min() {
+        let mut h: ArrayMinMaxHeap<[usize; 3]> = ArrayMinMaxHeap::new();
+
+        assert_eq!(h.push_pop_min(1), 1);
+
+        for &x in &[4, 2, 6] {
+            assert!(h.push(x).is_ok());
+        }
+
+        assert_eq!(h.push_pop_min(1), 1);
+        assert_eq!(h.push_pop_min(5), 2);
+        assert_eq!(h.push_pop_min(9), 4);
+        assert_eq!(h.peek_min(), Some(&5));
+        assert_eq!(h.peek_max(), Some(&9));
+        assert_eq!(h.len(), 3);
+    }
+}
@@ -0,0 +1,328 @@
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug, Formatter};
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{self, AtomicUsize, Ordering};
+
+use arrayvec::Array;
+
+use super::array_shape::ArrayShape;
+use super::error::CapacityError;
+
+struct Slot<T> {
+    stamp: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// A lock-free bounded multi-producer multi-consumer queue.
+///
+/// The head, the tail and each slot carry stamps which combine a lap number in
+/// their upper bits with a slot index in their lower bits. A slot's stamp
+/// tells producers and consumers whether it is ready to be written or read on
+/// the current lap. Only the lap number wraps around, so the queue keeps
+/// working after the counters overflow whatever its capacity is.
+pub struct ArrayMpmcQueue<A: ArrayShape> {
+    slots: A::With<Slot<<A as Array>::Item>>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+unsafe impl<T: Send, A: ArrayShape<Item = T>> Send for ArrayMpmcQueue<A> {}
+unsafe impl<T: Send, A: ArrayShape<Item = T>> Sync for ArrayMpmcQueue<A> {}
+
+impl<T, A: ArrayShape<Item = T>> ArrayMpmcQueue<A> {
+    pub fn new() -> Self {
+        Self::with_lap(0)
+    }
+
+    fn with_lap(lap: usize) -> Self {
+        ArrayMpmcQueue {
+            slots: A::with_fn(|i| Slot {
+                stamp: AtomicUsize::new(lap + i),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            }),
+            head: AtomicUsize::new(lap),
+            tail: AtomicUsize::new(lap),
+        }
+    }
+
+    pub fn try_push(&self, x: T) -> Result<(), CapacityError<T>> {
+        let n = self.capacity();
+
+        if n == 0 {
+            return Err(CapacityError::new(x));
+        }
+
+        let one_lap = self.one_lap();
+        let mut tail = self.tail.load(Ordering::Relaxed);
+
+        loop {
+            let i = tail & (one_lap - 1);
+            let slot = &self.slots.as_ref()[i];
+            let stamp = slot.stamp.load(Ordering::Acquire);
+
+            if stamp == tail {
+                let next = if i + 1 < n {
+                    tail + 1
+                } else {
+                    (tail & !(one_lap - 1)).wrapping_add(one_lap)
+                };
+
+                match self.tail.compare_exchange_weak(
+                    tail,
+                    next,
+                    Ordering::SeqCst,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe { ptr::write((*slot.value.get()).as_mut_ptr(), x) };
+                        slot.stamp.store(tail + 1, Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(t) => tail = t,
+                }
+            } else if stamp.wrapping_add(one_lap) == tail + 1 {
+                // The slot still holds an element from the previous lap.
+                atomic::fence(Ordering::SeqCst);
+
+                if self.head.load(Ordering::Relaxed).wrapping_add(one_lap) == tail {
+                    return Err(CapacityError::new(x));
+                }
+
+                tail = self.tail.load(Ordering::Relaxed);
+            } else {
+                tail = self.tail.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn try_pop(&self) -> Option<T> {
+        let n = self.capacity();
+
+        if n == 0 {
+            return None;
+        }
+
+        let one_lap = self.one_lap();
+        let mut head = self.head.load(Ordering::Relaxed);
+
+        loop {
+            let i = head & (one_lap - 1);
+            let slot = &self.slots.as_ref()[i];
+            let stamp = slot.stamp.load(Ordering::Acquire);
+
+            if stamp == head + 1 {
+                let next = if i + 1 < n {
+                    head + 1
+                } else {
+                    (head & !(one_lap - 1)).wrapping_add(one_lap)
+                };
+
+                match self.head.compare_exchange_weak(
+                    head,
+                    next,
+                    Ordering::SeqCst,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let x = unsafe { ptr::read((*slot.value.get()).as_ptr()) };
+                        slot.stamp
+                            .store(head.wrapping_add(one_lap), Ordering::Release);
+                        return Some(x);
+                    }
+                    Err(h) => head = h,
+                }
+            } else if stamp == head {
+                // The slot has not been written on this lap yet.
+                atomic::fence(Ordering::SeqCst);
+
+                if self.tail.load(Ordering::Relaxed) == head {
+                    return None;
+                }
+
+                head = self.head.load(Ordering::Relaxed);
+            } else {
+                head = self.head.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Returns the number of elements. The value can be stale by the time it
+    /// is returned if other threads are pushing or popping concurrently.
+    pub fn len(&self) -> usize {
+        let mask = self.one_lap() - 1;
+
+        loop {
+            let tail = self.tail.load(Ordering::SeqCst);
+            let head = self.head.load(Ordering::SeqCst);
+
+            if self.tail.load(Ordering::SeqCst) == tail {
+                let (h, t) = (head & mask, tail & mask);
+
+                return if h < t {
+                    t - h
+                } else if h > t {
+                    self.capacity() - h + t
+                } else if head == tail {
+                    0
+                } else {
+                    self.capacity()
+                };
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    fn one_lap(&self) -> usize {
+        (self.capacity() + 1).next_power_of_two()
+    }
+}
+
+impl<T, A: ArrayShape<Item = T>> Debug for ArrayMpmcQueue<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ArrayMpmcQueue")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T, A: ArrayShape<Item = T>> Default for ArrayMpmcQueue<A> {
+    fn default() -> Self {
+        ArrayMpmcQueue::new()
+    }
+}
+
+impl<T, A: ArrayShape<Item = T>> Drop for ArrayMpmcQueue<A> {
+    fn drop(&mut self) {
+        while self.try_pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::Mutex;
+    use std::thread;
+
+    #[test]
+    fn new() {
+        ArrayMpmcQueue::<[usize; 0]>::new();
+        ArrayMpmcQueue::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let q: ArrayMpmcQueue<[usize; 2]> = ArrayMpmcQueue::new();
+
+        assert_eq!(q.try_pop(), None);
+        assert!(q.try_push(1).is_ok());
+        assert!(q.try_push(2).is_ok());
+        assert_eq!(q.try_push(3), Err(CapacityError::new(3)));
+        assert!(q.is_full());
+        assert_eq!(q.try_pop(), Some(1));
+        assert!(q.try_push(3).is_ok());
+        assert_eq!(q.try_pop(), Some(2));
+        assert_eq!(q.try_pop(), Some(3));
+        assert_eq!(q.try_pop(), None);
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let q: ArrayMpmcQueue<[usize; 0]> = ArrayMpmcQueue::new();
+
+        assert_eq!(q.try_push(1), Err(CapacityError::new(1)));
+        assert_eq!(q.try_pop(), None);
+    }
+
+    #[test]
+    fn wrap_around_counters() {
+        let q: ArrayMpmcQueue<[usize; 3]> = ArrayMpmcQueue::with_lap(usize::MAX & !3);
+
+        for i in 0..10 {
+            assert!(q.try_push(2 * i).is_ok());
+            assert!(q.try_push(2 * i + 1).is_ok());
+            assert_eq!(q.len(), 2);
+            assert_eq!(q.try_pop(), Some(2 * i));
+            assert_eq!(q.try_pop(), Some(2 * i + 1));
+            assert!(q.is_empty());
+        }
+
+        for i in 0..3 {
+            assert!(q.try_push(i).is_ok());
+        }
+
+        assert_eq!(q.try_push(3), Err(CapacityError::new(3)));
+        assert!(q.is_full());
+    }
+
+    #[test]
+    fn drop_remaining_elements() {
+        let q: ArrayMpmcQueue<[Box<usize>; 4]> = ArrayMpmcQueue::new();
+
+        assert!(q.try_push(Box::new(1)).is_ok());
+        assert!(q.try_push(Box::new(2)).is_ok());
+        assert_eq!(q.len(), 2);
+    }
+
+    #[test]
+    fn threads() {
+        const THREADS: usize = 4;
+        const N: usize = 1000;
+
+        let q: ArrayMpmcQueue<[usize; 8]> = ArrayMpmcQueue::new();
+        let xs = Mutex::new(vec![]);
+
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let q = &q;
+
+                s.spawn(move || {
+                    for i in 0..N {
+                        let mut x = t * N + i;
+
+                        while let Err(e) = q.try_push(x) {
+                            x = e.element();
+                            thread::yield_now();
+                        }
+                    }
+                });
+            }
+
+            for _ in 0..THREADS {
+                let (q, xs) = (&q, &xs);
+
+                s.spawn(move || {
+                    for _ in 0..N {
+                        loop {
+                            if let Some(x) = q.try_pop() {
+                                xs.lock().unwrap().push(x);
+                                break;
+                            }
+
+                            thread::yield_now();
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut xs = xs.into_inner().unwrap();
+        xs.sort();
+
+        assert_eq!(xs, (0..THREADS * N).collect::<Vec<_>>());
+    }
+}
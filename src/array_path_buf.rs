@@ -0,0 +1,177 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Deref;
+use std::path::{Path, MAIN_SEPARATOR};
+
+use arrayvec::Array;
+
+use super::array_string::ArrayString;
+use super::error::CapacityError;
+
+pub struct ArrayPathBuf<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> {
+    string: ArrayString<A>,
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> ArrayPathBuf<A> {
+    pub fn new() -> Self {
+        ArrayPathBuf {
+            string: ArrayString::new(),
+        }
+    }
+
+    pub fn push(&mut self, s: &str) -> Result<(), CapacityError> {
+        if Path::new(s).has_root() {
+            if s.len() > self.capacity() {
+                return Err(CapacityError::new(()));
+            }
+
+            self.string.clear();
+            return self.string.push_str(s);
+        }
+
+        let l = self.string.len();
+        let separated = self.string.is_empty() || self.string.ends_with(MAIN_SEPARATOR);
+
+        let result = if separated {
+            self.string.push_str(s)
+        } else {
+            self.string
+                .push(MAIN_SEPARATOR)
+                .map_err(|_| CapacityError::new(()))
+                .and_then(|_| self.string.push_str(s))
+        };
+
+        if result.is_err() {
+            self.string.truncate(l);
+        }
+
+        result
+    }
+
+    pub fn pop(&mut self) -> bool {
+        match self.as_path().parent().map(|p| p.as_os_str().len()) {
+            Some(l) => {
+                self.string.truncate(l);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.string.clear();
+    }
+
+    pub fn as_path(&self) -> &Path {
+        Path::new(self.string.as_str())
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.string.as_str()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.string.capacity()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> AsRef<Path> for ArrayPathBuf<A> {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Clone for ArrayPathBuf<A> {
+    fn clone(&self) -> Self {
+        ArrayPathBuf {
+            string: self.string.clone(),
+        }
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Debug for ArrayPathBuf<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(self.as_path(), f)
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Default for ArrayPathBuf<A> {
+    fn default() -> Self {
+        ArrayPathBuf::new()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Deref for ArrayPathBuf<A> {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> PartialEq for ArrayPathBuf<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_path() == other.as_path()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Eq for ArrayPathBuf<A> {}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        assert_eq!(ArrayPathBuf::<[u8; 8]>::new().as_path(), Path::new(""));
+    }
+
+    #[test]
+    fn push() {
+        let mut p: ArrayPathBuf<[u8; 16]> = ArrayPathBuf::new();
+
+        assert!(p.push("foo").is_ok());
+        assert!(p.push("bar").is_ok());
+        assert_eq!(p.as_path(), Path::new("foo/bar"));
+
+        assert!(p.push("/baz").is_ok());
+        assert_eq!(p.as_path(), Path::new("/baz"));
+
+        assert!(p.push("qux/").is_ok());
+        assert!(p.push("a").is_ok());
+        assert_eq!(p.as_str(), "/baz/qux/a");
+    }
+
+    #[test]
+    fn push_over_capacity() {
+        let mut p: ArrayPathBuf<[u8; 8]> = ArrayPathBuf::new();
+
+        assert!(p.push("foo").is_ok());
+        assert_eq!(p.push("barrr"), Err(CapacityError::new(())));
+        assert_eq!(p.push("/123456789"), Err(CapacityError::new(())));
+        assert_eq!(p.as_str(), "foo");
+        assert!(p.push("bar").is_ok());
+        assert_eq!(p.as_str(), "foo/bar");
+    }
+
+    #[test]
+    fn pop() {
+        let mut p: ArrayPathBuf<[u8; 16]> = ArrayPathBuf::new();
+
+        assert!(p.push("/foo/bar").is_ok());
+        assert!(p.pop());
+        assert_eq!(p.as_path(), Path::new("/foo"));
+        assert!(p.pop());
+        assert_eq!(p.as_path(), Path::new("/"));
+        assert!(!p.pop());
+        assert_eq!(p.as_path(), Path::new("/"));
+    }
+
+    #[test]
+    fn deref() {
+        let mut p: ArrayPathBuf<[u8; 16]> = ArrayPathBuf::new();
+
+        assert!(p.push("foo/bar.txt").is_ok());
+        assert_eq!(p.extension().unwrap(), "txt");
+        assert_eq!(p.file_name().unwrap(), "bar.txt");
+    }
+}
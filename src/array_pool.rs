@@ -0,0 +1,217 @@
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::array_shape::ArrayShape;
+
+/// A pool of reusable objects.
+///
+/// Objects are lent out through guards which return them to the pool when
+/// they are dropped. Objects keep their states between uses.
+pub struct ArrayPool<A: ArrayShape> {
+    objects: UnsafeCell<A>,
+    used: A::With<AtomicBool>,
+}
+
+unsafe impl<T: Send, A: ArrayShape<Item = T>> Send for ArrayPool<A> {}
+// SAFETY: Sharing the pool only lets threads acquire guards. Each flag in
+// `used` is claimed by exactly one guard at a time, so an object is never
+// reachable from two threads at once and `&mut T` is only ever moved between
+// threads, never shared. That is what `T: Send` permits; `T: Sync` is not
+// needed because no `&T` is shared either.
+unsafe impl<T: Send, A: ArrayShape<Item = T>> Sync for ArrayPool<A> {}
+
+impl<T, A: ArrayShape<Item = T>> ArrayPool<A> {
+    pub fn new() -> Self
+    where
+        T: Default,
+    {
+        Self::from_fn(|_| T::default())
+    }
+
+    pub fn from_fn<F: FnMut(usize) -> T>(f: F) -> Self {
+        ArrayPool {
+            objects: UnsafeCell::new(A::from_fn(f)),
+            used: A::with_fn(|_| AtomicBool::new(false)),
+        }
+    }
+
+    /// Panics if all objects are in use.
+    pub fn acquire(&self) -> ArrayPoolGuard<'_, T> {
+        self.try_acquire().expect("no available object in pool")
+    }
+
+    pub fn try_acquire(&self) -> Option<ArrayPoolGuard<'_, T>> {
+        let p = self.objects.get() as *mut T;
+
+        for (i, used) in self.used.as_ref().iter().enumerate() {
+            if used
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Some(ArrayPoolGuard {
+                    object: unsafe { &mut *p.add(i) },
+                    used,
+                });
+            }
+        }
+
+        None
+    }
+
+    pub fn available(&self) -> usize {
+        self.used
+            .as_ref()
+            .iter()
+            .filter(|used| !used.load(Ordering::Relaxed))
+            .count()
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    pub fn into_inner(self) -> A {
+        self.objects.into_inner()
+    }
+}
+
+impl<T, A: ArrayShape<Item = T>> Debug for ArrayPool<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ArrayPool")
+            .field("available", &self.available())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T: Default, A: ArrayShape<Item = T>> Default for ArrayPool<A> {
+    fn default() -> Self {
+        ArrayPool::new()
+    }
+}
+
+pub struct ArrayPoolGuard<'a, T: 'a> {
+    object: &'a mut T,
+    used: &'a AtomicBool,
+}
+
+impl<'a, T: Debug> Debug for ArrayPoolGuard<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("ArrayPoolGuard").field(self.object).finish()
+    }
+}
+
+impl<'a, T> Deref for ArrayPoolGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.object
+    }
+}
+
+impl<'a, T> DerefMut for ArrayPoolGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.object
+    }
+}
+
+impl<'a, T> Drop for ArrayPoolGuard<'a, T> {
+    fn drop(&mut self) {
+        self.used.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[test]
+    fn new() {
+        ArrayPool::<[usize; 0]>::new();
+        ArrayPool::<[[u8; 16]; 8]>::new();
+    }
+
+    #[test]
+    fn acquire() {
+        let p: ArrayPool<[usize; 2]> = ArrayPool::from_fn(|i| i);
+
+        let mut x = p.acquire();
+        let y = p.acquire();
+
+        assert_eq!(*x, 0);
+        assert_eq!(*y, 1);
+        assert!(p.try_acquire().is_none());
+        assert_eq!(p.available(), 0);
+
+        *x = 42;
+        drop(x);
+
+        assert_eq!(p.available(), 1);
+        assert_eq!(*p.acquire(), 42);
+
+        drop(y);
+
+        assert_eq!(p.into_inner(), [42, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn acquire_from_exhausted_pool() {
+        let p: ArrayPool<[usize; 0]> = ArrayPool::new();
+
+        p.acquire();
+    }
+
+    #[test]
+    fn acquire_from_two_threads() {
+        let p: ArrayPool<[usize; 2]> = ArrayPool::from_fn(|i| i);
+        let b = Barrier::new(2);
+
+        let xs = thread::scope(|s| {
+            let hs: Vec<_> = (0..2)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut x = p.acquire();
+                        *x += 10;
+                        // Both guards are held at once before either is released.
+                        b.wait();
+                        *x
+                    })
+                })
+                .collect();
+
+            hs.into_iter()
+                .map(|h| h.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(xs.iter().sum::<usize>(), 21);
+        assert_eq!(p.available(), 2);
+        assert_eq!(p.into_inner(), [10, 11]);
+    }
+
+    #[test]
+    fn threads() {
+        let p: ArrayPool<[usize; 2]> = ArrayPool::new();
+
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..1000 {
+                        if let Some(mut x) = p.try_acquire() {
+                            *x += 1;
+                        }
+                    }
+                });
+            }
+        });
+
+        assert_eq!(p.available(), 2);
+        assert!(p.into_inner().iter().sum::<usize>() <= 4000);
+    }
+}
@@ -1,12 +1,25 @@
-use std::mem::{drop, forget, replace, uninitialized, ManuallyDrop};
+use std::array;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, Read, Write};
+use std::iter::{FromIterator, FusedIterator};
+#[cfg(feature = "serde")]
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::slice;
 
 use arrayvec::Array;
+#[cfg(feature = "serde")]
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
 
 use super::error::CapacityError;
 
-#[derive(Debug)]
 pub struct ArrayQueue<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
-    array: ManuallyDrop<A>,
+    array: MaybeUninit<A>,
     start: usize,
     length: usize,
 }
@@ -14,12 +27,42 @@ pub struct ArrayQueue<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Arra
 impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> ArrayQueue<A> {
     pub fn new() -> Self {
         ArrayQueue {
-            array: unsafe { uninitialized() },
+            array: MaybeUninit::uninit(),
             start: 0,
             length: 0,
         }
     }
 
+    pub fn filled(x: <A as Array>::Item) -> Self
+    where
+        <A as Array>::Item: Clone,
+    {
+        let mut a = Self::new();
+
+        while !a.is_full() {
+            a.write_back(x.clone());
+        }
+
+        a
+    }
+
+    pub fn from_fn<F: FnMut(usize) -> <A as Array>::Item>(
+        n: usize,
+        mut f: F,
+    ) -> Result<Self, CapacityError> {
+        let mut a = Self::new();
+
+        if n > a.capacity() {
+            return Err(CapacityError::new(()));
+        }
+
+        for i in 0..n {
+            a.write_back(f(i));
+        }
+
+        Ok(a)
+    }
+
     pub fn first(&self) -> Option<&<A as Array>::Item> {
         self.element(0)
     }
@@ -45,49 +88,148 @@ impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Array
         self.element_mut(i)
     }
 
-    fn element(&self, i: usize) -> Option<&<A as Array>::Item> {
-        if self.is_empty() {
+    pub fn get_many_mut<const K: usize>(
+        &mut self,
+        is: [usize; K],
+    ) -> Option<[&mut <A as Array>::Item; K]> {
+        for (k, &i) in is.iter().enumerate() {
+            if i >= self.len() || is[..k].contains(&i) {
+                return None;
+            }
+        }
+
+        let ps: [*mut <A as Array>::Item; K] = array::from_fn(|k| self.pointer_mut(is[k]));
+
+        Some(ps.map(|p| unsafe { &mut *p }))
+    }
+
+    pub fn contains(&self, x: &<A as Array>::Item) -> bool
+    where
+        <A as Array>::Item: PartialEq,
+    {
+        self.into_iter().any(|y| y == x)
+    }
+
+    pub fn binary_search(&self, x: &<A as Array>::Item) -> Result<usize, usize>
+    where
+        <A as Array>::Item: Ord,
+    {
+        self.binary_search_by(|y| y.cmp(x))
+    }
+
+    pub fn binary_search_by<F: FnMut(&<A as Array>::Item) -> Ordering>(
+        &self,
+        mut f: F,
+    ) -> Result<usize, usize> {
+        let (xs, ys) = self.as_slices();
+
+        match ys.first().map(&mut f) {
+            Some(Ordering::Equal) => Ok(xs.len()),
+            Some(Ordering::Less) => ys
+                .binary_search_by(f)
+                .map(|i| i + xs.len())
+                .map_err(|i| i + xs.len()),
+            _ => xs.binary_search_by(f),
+        }
+    }
+
+    pub fn binary_search_by_key<K: Ord, F: FnMut(&<A as Array>::Item) -> K>(
+        &self,
+        k: &K,
+        mut f: F,
+    ) -> Result<usize, usize> {
+        self.binary_search_by(|x| f(x).cmp(k))
+    }
+
+    pub(crate) fn element(&self, i: usize) -> Option<&<A as Array>::Item> {
+        if i >= self.len() {
             None
         } else {
-            Some(&self.array.as_ref()[self.index(i)])
+            Some(unsafe { &*self.pointer(i) })
         }
     }
 
-    fn element_mut(&mut self, i: usize) -> Option<&mut <A as Array>::Item> {
-        if self.is_empty() {
+    pub(crate) fn element_mut(&mut self, i: usize) -> Option<&mut <A as Array>::Item> {
+        if i >= self.len() {
             None
         } else {
-            let i = self.index(i);
-            Some(&mut self.array.as_mut()[i])
+            Some(unsafe { &mut *self.pointer_mut(i) })
+        }
+    }
+
+    pub fn push_back(
+        &mut self,
+        x: <A as Array>::Item,
+    ) -> Result<(), CapacityError<<A as Array>::Item>> {
+        if self.is_full() {
+            return Err(CapacityError::new(x));
+        }
+
+        self.write_back(x);
+        Ok(())
+    }
+
+    pub fn push_front(
+        &mut self,
+        x: <A as Array>::Item,
+    ) -> Result<(), CapacityError<<A as Array>::Item>> {
+        if self.is_full() {
+            return Err(CapacityError::new(x));
         }
+
+        self.write_front(x);
+        Ok(())
     }
 
-    pub fn push_back(&mut self, x: &<A as Array>::Item) -> Result<(), CapacityError>
+    pub fn try_extend_from_slice(&mut self, xs: &[<A as Array>::Item]) -> Result<(), CapacityError>
     where
         <A as Array>::Item: Clone,
     {
-        if self.is_full() {
-            return Err(CapacityError);
+        if xs.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(()));
+        } else if xs.is_empty() {
+            return Ok(());
         }
 
         let i = self.index(self.length);
-        forget(replace(&mut self.array.as_mut()[i], x.clone()));
-        self.length += 1;
+        let (ys, zs) = xs.split_at(xs.len().min(self.capacity() - i));
+
+        for &(j, ws) in [(i, ys), (0, zs)].iter() {
+            let p = self.as_mut_ptr() as *mut MaybeUninit<<A as Array>::Item>;
+            let us = unsafe { slice::from_raw_parts_mut(p.add(j), ws.len()) };
+
+            for (u, w) in us.iter_mut().zip(ws) {
+                *u = MaybeUninit::new(w.clone());
+            }
+
+            self.length += ws.len();
+        }
+
         Ok(())
     }
 
-    pub fn push_front(&mut self, x: &<A as Array>::Item) -> Result<(), CapacityError>
+    pub fn push_back_slice(&mut self, xs: &[<A as Array>::Item]) -> usize
     where
         <A as Array>::Item: Clone,
     {
-        if self.is_full() {
-            return Err(CapacityError);
+        let n = xs.len().min(self.remaining_capacity());
+        self.try_extend_from_slice(&xs[..n]).unwrap();
+        n
+    }
+
+    pub fn push_back_overwrite(&mut self, x: <A as Array>::Item) -> Option<<A as Array>::Item> {
+        if self.capacity() == 0 {
+            return Some(x);
         }
 
-        self.start = self.index(Self::capacity() - 1);
-        forget(replace(&mut self.array.as_mut()[self.start], x.clone()));
-        self.length += 1;
-        Ok(())
+        let y = if self.is_full() {
+            self.pop_front()
+        } else {
+            None
+        };
+
+        self.write_back(x);
+        y
     }
 
     pub fn pop_back(&mut self) -> Option<<A as Array>::Item> {
@@ -95,9 +237,7 @@ impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Array
             return None;
         }
 
-        let x = replace(&mut self.array.as_mut()[self.length - 1], unsafe {
-            uninitialized()
-        });
+        let x = unsafe { ptr::read(self.pointer(self.length - 1)) };
         self.length -= 1;
         Some(x)
     }
@@ -107,14 +247,118 @@ impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Array
             return None;
         }
 
-        let x = replace(&mut self.array.as_mut()[self.start], unsafe {
-            uninitialized()
-        });
+        let x = unsafe { ptr::read(self.pointer(0)) };
         self.start = self.index(1);
         self.length -= 1;
         Some(x)
     }
 
+    pub fn insert(
+        &mut self,
+        i: usize,
+        x: <A as Array>::Item,
+    ) -> Result<(), CapacityError<<A as Array>::Item>> {
+        assert!(i <= self.len(), "index out of bounds");
+
+        if self.is_full() {
+            return Err(CapacityError::new(x));
+        }
+
+        if i < self.len() - i {
+            self.start = self.index(self.capacity() - 1);
+
+            for j in 0..i {
+                self.move_element(j + 1, j);
+            }
+        } else {
+            for j in (i..self.len()).rev() {
+                self.move_element(j, j + 1);
+            }
+        }
+
+        unsafe { ptr::write(self.pointer_mut(i), x) };
+        self.length += 1;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, i: usize) -> Option<<A as Array>::Item> {
+        if i >= self.len() {
+            return None;
+        }
+
+        let x = unsafe { ptr::read(self.pointer(i)) };
+
+        if i < self.len() - 1 - i {
+            for j in (0..i).rev() {
+                self.move_element(j, j + 1);
+            }
+
+            self.start = self.index(1);
+        } else {
+            for j in i..self.len() - 1 {
+                self.move_element(j + 1, j);
+            }
+        }
+
+        self.length -= 1;
+        Some(x)
+    }
+
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len() && j < self.len(), "index out of bounds");
+
+        unsafe { ptr::swap(self.pointer_mut(i), self.pointer_mut(j)) };
+    }
+
+    pub fn swap_remove_back(&mut self, i: usize) -> Option<<A as Array>::Item> {
+        if i >= self.len() {
+            return None;
+        }
+
+        let j = self.len() - 1;
+        unsafe { ptr::swap(self.pointer_mut(i), self.pointer_mut(j)) };
+        self.pop_back()
+    }
+
+    pub fn swap_remove_front(&mut self, i: usize) -> Option<<A as Array>::Item> {
+        if i >= self.len() {
+            return None;
+        }
+
+        unsafe { ptr::swap(self.pointer_mut(i), self.pointer_mut(0)) };
+        self.pop_front()
+    }
+
+    pub fn pop_front_into(&mut self, xs: &mut [<A as Array>::Item]) -> usize {
+        let n = xs.len().min(self.len());
+
+        if n == 0 {
+            return 0;
+        }
+
+        let m = n.min(self.slice_lengths().0);
+        let p = self.pointer_mut(0);
+        let q = self.as_mut_ptr();
+        let r = xs.as_mut_ptr();
+
+        // Swap rather than copy so that the old values of `xs` end up in the
+        // vacated slots and are dropped only after both sides are consistent.
+        unsafe {
+            ptr::swap_nonoverlapping(p, r, m);
+            ptr::swap_nonoverlapping(q, r.add(m), n - m);
+        }
+
+        self.start = self.index(n);
+        self.length -= n;
+
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(p, m));
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(q, n - m));
+        }
+
+        n
+    }
+
     pub fn len(&self) -> usize {
         self.length
     }
@@ -124,15 +368,242 @@ impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Array
     }
 
     pub fn is_full(&self) -> bool {
-        self.len() == Self::capacity()
+        self.len() == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    pub fn as_slices(&self) -> (&[<A as Array>::Item], &[<A as Array>::Item]) {
+        let (l, m) = self.slice_lengths();
+
+        unsafe {
+            (
+                slice::from_raw_parts(self.as_ptr().add(self.start), l),
+                slice::from_raw_parts(self.as_ptr(), m),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [<A as Array>::Item], &mut [<A as Array>::Item]) {
+        let (l, m) = self.slice_lengths();
+        let p = self.as_mut_ptr();
+
+        unsafe {
+            (
+                slice::from_raw_parts_mut(p.add(self.start), l),
+                slice::from_raw_parts_mut(p, m),
+            )
+        }
+    }
+
+    /// Returns all elements as a single slice. Callers must keep the elements
+    /// from wrapping around the end of the backing array.
+    pub(crate) fn as_contiguous_slice(&self) -> &[<A as Array>::Item] {
+        let (xs, ys) = self.as_slices();
+        debug_assert!(ys.is_empty(), "queue is not contiguous");
+        xs
+    }
+
+    pub fn make_contiguous(&mut self) -> &mut [<A as Array>::Item] {
+        if self.start + self.length > self.capacity() {
+            let p = self.as_mut_ptr() as *mut MaybeUninit<<A as Array>::Item>;
+            unsafe { slice::from_raw_parts_mut(p, self.capacity()) }.rotate_left(self.start);
+            self.start = 0;
+        }
+
+        self.as_mut_slices().0
+    }
+
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len(), "rotation out of bounds");
+
+        if n == 0 || self.capacity() == 0 {
+            return;
+        }
+
+        if self.is_full() {
+            self.start = self.index(n);
+        } else if n <= self.len() - n {
+            for _ in 0..n {
+                let x = self.pop_front().unwrap();
+                self.write_back(x);
+            }
+        } else {
+            let n = self.len() - n;
+            self.rotate_right(n);
+        }
+    }
+
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len(), "rotation out of bounds");
+
+        if n == 0 || self.capacity() == 0 {
+            return;
+        }
+
+        if self.is_full() {
+            self.start = self.index(self.len() - n);
+        } else if n <= self.len() - n {
+            for _ in 0..n {
+                let x = self.pop_back().unwrap();
+                self.write_front(x);
+            }
+        } else {
+            let n = self.len() - n;
+            self.rotate_left(n);
+        }
+    }
+
+    pub fn append(&mut self, other: &mut Self) -> Result<(), CapacityError> {
+        if other.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(()));
+        }
+
+        while let Some(x) = other.pop_front() {
+            self.write_back(x);
+        }
+
+        Ok(())
+    }
+
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "index out of bounds");
+
+        let mut a = Self::new();
+
+        for i in at..self.len() {
+            a.write_back(unsafe { ptr::read(self.pointer(i)) });
+        }
+
+        self.length = at;
+        a
+    }
+
+    pub fn resize_with<F: FnMut() -> <A as Array>::Item>(
+        &mut self,
+        n: usize,
+        mut f: F,
+    ) -> Result<(), CapacityError> {
+        if n > self.capacity() {
+            return Err(CapacityError::new(()));
+        }
+
+        self.truncate_back(n);
+
+        while self.len() < n {
+            self.write_back(f());
+        }
+
+        Ok(())
+    }
+
+    pub fn fill(&mut self, x: &<A as Array>::Item)
+    where
+        <A as Array>::Item: Clone,
+    {
+        for y in self {
+            *y = x.clone();
+        }
+    }
+
+    pub fn truncate_back(&mut self, n: usize) {
+        while self.len() > n {
+            self.pop_back();
+        }
+    }
+
+    pub fn truncate_front(&mut self, n: usize) {
+        while self.len() > n {
+            self.pop_front();
+        }
+    }
+
+    /// Returns a pointer to the start of the backing array. Only the elements
+    /// in the regions returned by `as_slices` are initialized.
+    pub fn as_ptr(&self) -> *const <A as Array>::Item {
+        self.array.as_ptr() as *const <A as Array>::Item
+    }
+
+    /// Returns a mutable pointer to the start of the backing array. Only the
+    /// elements in the regions returned by `as_slices` are initialized.
+    pub fn as_mut_ptr(&mut self) -> *mut <A as Array>::Item {
+        self.array.as_mut_ptr() as *mut <A as Array>::Item
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+        self.start = 0;
+    }
+
+    pub fn drain(&mut self) -> ArrayQueueDrain<'_, A> {
+        ArrayQueueDrain { queue: self }
+    }
+
+    pub fn extract_if<F: FnMut(&mut <A as Array>::Item) -> bool>(
+        &mut self,
+        f: F,
+    ) -> ArrayQueueExtractIf<'_, A, F> {
+        let l = self.len();
+
+        ArrayQueueExtractIf {
+            queue: self,
+            rest: l,
+            predicate: f,
+        }
+    }
+
+    pub fn retain<F: FnMut(&<A as Array>::Item) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|x| f(x))
+    }
+
+    pub fn retain_mut<F: FnMut(&mut <A as Array>::Item) -> bool>(&mut self, mut f: F) {
+        for _ in 0..self.len() {
+            let mut x = self.pop_front().unwrap();
+
+            if f(&mut x) {
+                self.write_back(x);
+            }
+        }
+    }
+
+    fn write_back(&mut self, x: <A as Array>::Item) {
+        let i = self.length;
+        unsafe { ptr::write(self.pointer_mut(i), x) };
+        self.length += 1;
+    }
+
+    fn write_front(&mut self, x: <A as Array>::Item) {
+        self.start = self.index(self.capacity() - 1);
+        unsafe { ptr::write(self.pointer_mut(0), x) };
+        self.length += 1;
+    }
+
+    fn move_element(&mut self, from: usize, to: usize) {
+        unsafe { ptr::copy_nonoverlapping(self.pointer(from), self.pointer_mut(to), 1) };
     }
 
     fn index(&self, i: usize) -> usize {
-        (self.start + i) % Self::capacity()
+        (self.start + i) % self.capacity()
     }
 
-    fn capacity() -> usize {
-        A::capacity()
+    fn pointer(&self, i: usize) -> *const <A as Array>::Item {
+        unsafe { self.as_ptr().add(self.index(i)) }
+    }
+
+    fn pointer_mut(&mut self, i: usize) -> *mut <A as Array>::Item {
+        let i = self.index(i);
+        unsafe { self.as_mut_ptr().add(i) }
+    }
+
+    fn slice_lengths(&self) -> (usize, usize) {
+        let first = self.length.min(self.capacity() - self.start);
+        (first, self.length - first)
     }
 }
 
@@ -144,13 +615,22 @@ where
         let mut a = Self::new();
 
         for x in self {
-            a.push_back(x).unwrap();
+            a.write_back(x.clone());
         }
 
         a
     }
 }
 
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Debug for ArrayQueue<A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
 impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Default
     for ArrayQueue<A>
 {
@@ -159,37 +639,162 @@ impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Defau
     }
 }
 
-impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Drop for ArrayQueue<A> {
-    fn drop(&mut self) {
-        for x in self {
-            drop(replace(x, unsafe { uninitialized() }));
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> From<A>
+    for ArrayQueue<A>
+{
+    fn from(a: A) -> Self {
+        ArrayQueue {
+            array: MaybeUninit::new(a),
+            start: 0,
+            length: A::capacity(),
         }
     }
 }
 
-impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> IntoIterator
-    for &'a ArrayQueue<A>
+/// Panics if the queue runs out of capacity.
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>>
+    Extend<<A as Array>::Item> for ArrayQueue<A>
 {
-    type Item = &'a <A as Array>::Item;
-    type IntoIter = ArrayQueueIterator<'a, A>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        let l = self.len();
+    fn extend<I: IntoIterator<Item = <A as Array>::Item>>(&mut self, iter: I) {
+        for x in iter {
+            if self.is_full() {
+                panic!("{}", CapacityError::new(()));
+            }
 
-        ArrayQueueIterator {
-            queue: self,
-            first: 0,
-            last: l,
+            self.write_back(x);
         }
     }
 }
 
-impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> IntoIterator
-    for &'a mut ArrayQueue<A>
+/// Panics if the iterator yields more elements than the queue's capacity.
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>>
+    FromIterator<<A as Array>::Item> for ArrayQueue<A>
 {
-    type Item = &'a mut <A as Array>::Item;
-    type IntoIter = ArrayQueueMutIterator<'a, A>;
-
+    fn from_iter<I: IntoIterator<Item = <A as Array>::Item>>(iter: I) -> Self {
+        let mut a = Self::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> PartialEq
+    for ArrayQueue<A>
+where
+    <A as Array>::Item: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.into_iter().eq(other)
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Eq for ArrayQueue<A> where
+    <A as Array>::Item: Eq
+{
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> PartialOrd
+    for ArrayQueue<A>
+where
+    <A as Array>::Item: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.into_iter().partial_cmp(other)
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Ord for ArrayQueue<A>
+where
+    <A as Array>::Item: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.into_iter().cmp(other)
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Hash for ArrayQueue<A>
+where
+    <A as Array>::Item: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+
+        for x in self {
+            x.hash(state);
+        }
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Read for ArrayQueue<A> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.len());
+
+        {
+            let (xs, ys) = self.as_slices();
+            let m = n.min(xs.len());
+
+            buf[..m].copy_from_slice(&xs[..m]);
+            buf[m..n].copy_from_slice(&ys[..n - m]);
+        }
+
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> BufRead for ArrayQueue<A> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let n = amt.min(self.len());
+
+        if n > 0 {
+            self.start = self.index(n);
+            self.length -= n;
+        }
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Write for ArrayQueue<A> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.push_back_slice(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Drop for ArrayQueue<A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> IntoIterator
+    for &'a ArrayQueue<A>
+{
+    type Item = &'a <A as Array>::Item;
+    type IntoIter = ArrayQueueIterator<'a, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let l = self.len();
+
+        ArrayQueueIterator {
+            queue: self,
+            first: 0,
+            last: l,
+        }
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> IntoIterator
+    for &'a mut ArrayQueue<A>
+{
+    type Item = &'a mut <A as Array>::Item;
+    type IntoIter = ArrayQueueMutIterator<'a, A>;
+
     fn into_iter(self) -> Self::IntoIter {
         let l = self.len();
 
@@ -201,7 +806,58 @@ impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> I
     }
 }
 
-#[derive(Debug)]
+#[cfg(feature = "serde")]
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Serialize
+    for ArrayQueue<A>
+where
+    <A as Array>::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Deserialize<'de>
+    for ArrayQueue<A>
+where
+    <A as Array>::Item: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ArrayQueueVisitor(PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ArrayQueueVisitor<A>(PhantomData<A>);
+
+#[cfg(feature = "serde")]
+impl<'de, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Visitor<'de>
+    for ArrayQueueVisitor<A>
+where
+    <A as Array>::Item: Deserialize<'de>,
+{
+    type Value = ArrayQueue<A>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "a sequence of at most {} elements", A::capacity())
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut a = ArrayQueue::new();
+
+        while let Some(x) = seq.next_element()? {
+            if a.is_full() {
+                return Err(de::Error::invalid_length(a.len() + 1, &self));
+            }
+
+            a.write_back(x);
+        }
+
+        Ok(a)
+    }
+}
+
 pub struct ArrayQueueIterator<
     'a,
     A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
@@ -217,329 +873,1409 @@ impl<'a, A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item
     fn exhausted(&self) -> bool {
         self.first >= self.last
     }
-}
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Debug
+    for ArrayQueueIterator<'a, A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("ArrayQueueIterator")
+            .field(
+                &(self.first..self.last)
+                    .map(|i| unsafe { &*self.queue.pointer(i) })
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Iterator
+    for ArrayQueueIterator<'a, A>
+{
+    type Item = &'a <A as Array>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted() {
+            return None;
+        }
+
+        let x = unsafe { &*self.queue.pointer(self.first) };
+        self.first += 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.last - self.first;
+        (n, Some(n))
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> DoubleEndedIterator
+    for ArrayQueueIterator<'a, A>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.exhausted() {
+            return None;
+        }
+
+        self.last -= 1;
+        let x = unsafe { &*self.queue.pointer(self.last) };
+        Some(x)
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> ExactSizeIterator
+    for ArrayQueueIterator<'a, A>
+{
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> FusedIterator
+    for ArrayQueueIterator<'a, A>
+{
+}
+
+pub struct ArrayQueueMutIterator<
+    'a,
+    A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
+> {
+    queue: &'a mut ArrayQueue<A>,
+    first: usize,
+    last: usize,
+}
+
+impl<'a, A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>>
+    ArrayQueueMutIterator<'a, A>
+{
+    fn exhausted(&self) -> bool {
+        self.first >= self.last
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Debug
+    for ArrayQueueMutIterator<'a, A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("ArrayQueueMutIterator")
+            .field(
+                &(self.first..self.last)
+                    .map(|i| unsafe { &*self.queue.pointer(i) })
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Iterator
+    for ArrayQueueMutIterator<'a, A>
+{
+    type Item = &'a mut <A as Array>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted() {
+            return None;
+        }
+
+        let x = self.queue.pointer_mut(self.first);
+        self.first += 1;
+        Some(unsafe { &mut *x })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.last - self.first;
+        (n, Some(n))
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> DoubleEndedIterator
+    for ArrayQueueMutIterator<'a, A>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.exhausted() {
+            return None;
+        }
+
+        self.last -= 1;
+        let x = self.queue.pointer_mut(self.last);
+        Some(unsafe { &mut *x })
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> ExactSizeIterator
+    for ArrayQueueMutIterator<'a, A>
+{
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> FusedIterator
+    for ArrayQueueMutIterator<'a, A>
+{
+}
+
+pub struct ArrayQueueDrain<
+    'a,
+    A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
+> {
+    queue: &'a mut ArrayQueue<A>,
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Debug
+    for ArrayQueueDrain<'a, A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("ArrayQueueDrain").field(&self.queue).finish()
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Iterator
+    for ArrayQueueDrain<'a, A>
+{
+    type Item = <A as Array>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> ExactSizeIterator
+    for ArrayQueueDrain<'a, A>
+{
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> FusedIterator
+    for ArrayQueueDrain<'a, A>
+{
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Drop
+    for ArrayQueueDrain<'a, A>
+{
+    fn drop(&mut self) {
+        while self.queue.pop_front().is_some() {}
+    }
+}
+
+pub struct ArrayQueueExtractIf<
+    'a,
+    A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
+    F: FnMut(&mut <A as Array>::Item) -> bool,
+> {
+    queue: &'a mut ArrayQueue<A>,
+    rest: usize,
+    predicate: F,
+}
+
+impl<
+        'a,
+        A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
+        F: FnMut(&mut <A as Array>::Item) -> bool,
+    > Iterator for ArrayQueueExtractIf<'a, A, F>
+{
+    type Item = <A as Array>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.rest > 0 {
+            self.rest -= 1;
+            let mut x = self.queue.pop_front().unwrap();
+
+            if (self.predicate)(&mut x) {
+                return Some(x);
+            }
+
+            self.queue.write_back(x);
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.rest))
+    }
+}
+
+impl<
+        'a,
+        A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
+        F: FnMut(&mut <A as Array>::Item) -> bool,
+    > FusedIterator for ArrayQueueExtractIf<'a, A, F>
+{
+}
+
+impl<
+        'a,
+        A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
+        F: FnMut(&mut <A as Array>::Item) -> bool,
+    > Drop for ArrayQueueExtractIf<'a, A, F>
+{
+    fn drop(&mut self) {
+        if self.rest > 0 {
+            self.queue.rotate_left(self.rest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArrayQueue::<[usize; 1]>::new();
+        ArrayQueue::<[usize; 2]>::new();
+    }
+
+    #[test]
+    fn filled() {
+        let a: ArrayQueue<[Box<usize>; 3]> = ArrayQueue::filled(Box::new(42));
+
+        assert!(a.is_full());
+
+        for x in &a {
+            assert_eq!(**x, 42);
+        }
+
+        assert!(ArrayQueue::<[usize; 0]>::filled(42).is_empty());
+    }
+
+    #[test]
+    fn from_fn() {
+        let a: ArrayQueue<[usize; 4]> = ArrayQueue::from_fn(3, |i| i * 2).unwrap();
+
+        assert_eq!(a, [0, 2, 4].iter().cloned().collect());
+        assert_eq!(
+            ArrayQueue::<[usize; 2]>::from_fn(3, |i| i),
+            Err(CapacityError::new(()))
+        );
+    }
+
+    #[test]
+    fn from_array() {
+        let a = ArrayQueue::from([1, 2, 3]);
+
+        assert!(a.is_full());
+        assert_eq!(a.first(), Some(&1));
+        assert_eq!(a.last(), Some(&3));
+
+        let a = ArrayQueue::from([Box::new(1), Box::new(2)]);
+
+        assert!(a.is_full());
+        assert_eq!(a.first(), Some(&Box::new(1)));
+    }
+
+    #[test]
+    fn first_and_last() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert_eq!(a.first(), None);
+        assert_eq!(a.first_mut(), None);
+        assert_eq!(a.last(), None);
+        assert_eq!(a.last_mut(), None);
+
+        assert!(a.push_back(1).is_ok());
+
+        assert_eq!(a.first(), Some(&1));
+        assert_eq!(a.first_mut(), Some(&mut 1));
+        assert_eq!(a.last(), Some(&1));
+        assert_eq!(a.last_mut(), Some(&mut 1));
+
+        assert!(a.push_back(2).is_ok());
+
+        assert_eq!(a.first(), Some(&1));
+        assert_eq!(a.first_mut(), Some(&mut 1));
+        assert_eq!(a.last(), Some(&2));
+        assert_eq!(a.last_mut(), Some(&mut 2));
+    }
+
+    #[test]
+    fn get_many_mut() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_back(2).is_ok());
+        assert!(a.push_front(0).is_ok());
+
+        {
+            let [x, y] = a.get_many_mut([2, 0]).unwrap();
+            *x += 40;
+            *y += 40;
+        }
+
+        assert_eq!(a, [40, 1, 42].iter().cloned().collect());
+        assert!(a.get_many_mut([0, 0]).is_none());
+        assert!(a.get_many_mut([0, 3]).is_none());
+        assert_eq!(a.get_many_mut([]), Some([]));
+    }
+
+    #[test]
+    fn contains() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(!a.contains(&0));
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_front(1).is_ok());
+        assert!(a.contains(&0));
+        assert!(a.contains(&1));
+        assert!(!a.contains(&2));
+    }
+
+    #[test]
+    fn binary_search() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert_eq!(a.binary_search(&0), Err(0));
+
+        assert!(a.push_back(2).is_ok());
+        assert!(a.push_back(4).is_ok());
+        assert!(a.push_front(0).is_ok());
+
+        assert_eq!(a.binary_search(&0), Ok(0));
+        assert_eq!(a.binary_search(&1), Err(1));
+        assert_eq!(a.binary_search(&2), Ok(1));
+        assert_eq!(a.binary_search(&3), Err(2));
+        assert_eq!(a.binary_search(&4), Ok(2));
+        assert_eq!(a.binary_search(&5), Err(3));
+    }
+
+    #[test]
+    fn binary_search_by_key() {
+        let mut a: ArrayQueue<[(usize, char); 4]> = ArrayQueue::new();
+
+        assert!(a.push_back((1, 'b')).is_ok());
+        assert!(a.push_front((0, 'a')).is_ok());
+
+        assert_eq!(a.binary_search_by_key(&0, |&(k, _)| k), Ok(0));
+        assert_eq!(a.binary_search_by_key(&1, |&(k, _)| k), Ok(1));
+        assert_eq!(a.binary_search_by_key(&2, |&(k, _)| k), Err(2));
+    }
+
+    #[test]
+    fn push_back() {
+        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+
+        assert_eq!(a.len(), 0);
+        assert!(a.push_back(42).is_ok());
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.push_back(42), Err(CapacityError::new(42)));
+        assert_eq!(a.len(), 1);
+
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert_eq!(a.len(), 0);
+        assert!(a.push_back(42).is_ok());
+        assert_eq!(a.len(), 1);
+        assert!(a.push_back(42).is_ok());
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.push_back(42), Err(CapacityError::new(42)));
+        assert_eq!(a.len(), 2);
+    }
+
+    #[test]
+    fn push_back_non_clone_element() {
+        struct Baz(usize);
+
+        let mut a: ArrayQueue<[Baz; 1]> = ArrayQueue::new();
+
+        assert!(a.push_back(Baz(1)).is_ok());
+        assert_eq!(a.push_back(Baz(2)).unwrap_err().element().0, 2);
+        assert_eq!(a.push_front(Baz(3)).unwrap_err().element().0, 3);
+        assert_eq!(a.first().map(|x| x.0), Some(1));
+    }
+
+    #[test]
+    fn push_front() {
+        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+
+        assert_eq!(a.len(), 0);
+        assert!(a.push_front(42).is_ok());
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.push_front(42), Err(CapacityError::new(42)));
+        assert_eq!(a.len(), 1);
+
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert_eq!(a.len(), 0);
+        assert!(a.push_front(1).is_ok());
+        assert_eq!(a.first(), Some(&1));
+        assert_eq!(a.last(), Some(&1));
+        assert_eq!(a.len(), 1);
+        assert!(a.push_front(2).is_ok());
+        assert_eq!(a.first(), Some(&2));
+        assert_eq!(a.last(), Some(&1));
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.push_front(3), Err(CapacityError::new(3)));
+        assert_eq!(a.len(), 2);
+    }
+
+    #[test]
+    fn try_extend_from_slice() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert!(a.try_extend_from_slice(&[]).is_ok());
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_back(0).is_ok());
+        a.pop_front();
+        a.pop_front();
+
+        assert!(a.try_extend_from_slice(&[0, 1, 2]).is_ok());
+        assert_eq!(a.as_slices(), (&[0, 1][..], &[2][..]));
+        assert_eq!(
+            a.try_extend_from_slice(&[3, 4]),
+            Err(CapacityError::new(()))
+        );
+        assert_eq!(a.len(), 3);
+        assert!(a.try_extend_from_slice(&[3]).is_ok());
+        assert_eq!(a.as_slices(), (&[0, 1][..], &[2, 3][..]));
+
+        let mut a: ArrayQueue<[Box<usize>; 2]> = ArrayQueue::new();
+
+        assert!(a.try_extend_from_slice(&[Box::new(0), Box::new(1)]).is_ok());
+        assert_eq!(a.last(), Some(&Box::new(1)));
+    }
+
+    #[test]
+    fn push_back_slice() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert_eq!(a.push_back_slice(&[]), 0);
+        assert!(a.push_front(0).is_ok());
+        assert_eq!(a.push_back_slice(&[1, 2]), 2);
+        assert_eq!(a.push_back_slice(&[3, 4]), 1);
+        assert_eq!(a.push_back_slice(&[5]), 0);
+        assert_eq!(a, (0..4).collect());
+    }
+
+    #[test]
+    fn push_back_overwrite() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert_eq!(a.push_back_overwrite(1), None);
+        assert_eq!(a.push_back_overwrite(2), None);
+        assert_eq!(a.push_back_overwrite(3), Some(1));
+        assert_eq!(a.push_back_overwrite(4), Some(2));
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.first(), Some(&3));
+        assert_eq!(a.last(), Some(&4));
+
+        let mut a: ArrayQueue<[usize; 0]> = ArrayQueue::new();
+
+        assert_eq!(a.push_back_overwrite(1), Some(1));
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn pop_back() {
+        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+
+        assert!(a.push_back(42).is_ok());
+
+        assert_eq!(a.pop_back(), Some(42));
+        assert_eq!(a.len(), 0);
+
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(123).is_ok());
+        assert!(a.push_back(42).is_ok());
+
+        assert_eq!(a.pop_back(), Some(42));
+        assert_eq!(a.first(), Some(&123));
+        assert_eq!(a.last(), Some(&123));
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.pop_back(), Some(123));
+        assert_eq!(a.len(), 0);
+    }
+
+    #[test]
+    fn pop_front() {
+        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+
+        assert!(a.push_back(42).is_ok());
+
+        assert_eq!(a.pop_front(), Some(42));
+        assert_eq!(a.len(), 0);
+
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(123).is_ok());
+        assert!(a.push_back(42).is_ok());
+
+        assert_eq!(a.pop_front(), Some(123));
+        assert_eq!(a.first(), Some(&42));
+        assert_eq!(a.last(), Some(&42));
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.pop_front(), Some(42));
+        assert_eq!(a.len(), 0);
+    }
+
+    #[test]
+    fn insert() {
+        for i in 0..4 {
+            let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+            assert!(a.push_back(1).is_ok());
+            assert!(a.push_back(2).is_ok());
+            assert!(a.push_front(0).is_ok());
+
+            assert!(a.insert(i, 42).is_ok());
+            assert_eq!(a.len(), 4);
+            assert_eq!(a.remove(i), Some(42));
+
+            for (j, x) in a.into_iter().enumerate() {
+                assert_eq!(*x, j);
+            }
+        }
+
+        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+
+        assert!(a.insert(0, 0).is_ok());
+        assert_eq!(a.insert(0, 1), Err(CapacityError::new(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        a.insert(1, 0).unwrap();
+    }
+
+    #[test]
+    fn remove() {
+        for i in 0..4 {
+            let mut a: ArrayQueue<[Box<usize>; 4]> = ArrayQueue::new();
+
+            for j in 1..4 {
+                assert!(a.push_back(Box::new(j)).is_ok());
+            }
+
+            assert!(a.push_front(Box::new(0)).is_ok());
+
+            assert_eq!(a.remove(i), Some(Box::new(i)));
+            assert_eq!(a.len(), 3);
+
+            let xs: Vec<usize> = a.into_iter().map(|x| **x).collect();
+            let ys: Vec<usize> = (0..4).filter(|&j| j != i).collect();
+
+            assert_eq!(xs, ys);
+        }
+
+        assert_eq!(ArrayQueue::<[usize; 1]>::new().remove(0), None);
+    }
+
+    #[test]
+    fn swap() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_back(2).is_ok());
+        assert!(a.push_front(0).is_ok());
+
+        a.swap(0, 2);
+        a.swap(1, 1);
+
+        assert_eq!(a, [2, 1, 0].iter().cloned().collect());
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_out_of_bounds() {
+        let mut a: ArrayQueue<[usize; 2]> = (0..1).collect();
+        a.swap(0, 1);
+    }
+
+    #[test]
+    fn swap_remove_back() {
+        let mut a: ArrayQueue<[usize; 4]> = (0..4).collect();
+
+        assert_eq!(a.swap_remove_back(4), None);
+        assert_eq!(a.swap_remove_back(1), Some(1));
+        assert_eq!(a.as_slices(), (&[0, 3, 2][..], &[][..]));
+        assert_eq!(a.swap_remove_back(2), Some(2));
+        assert_eq!(a.as_slices(), (&[0, 3][..], &[][..]));
+    }
+
+    #[test]
+    fn swap_remove_front() {
+        let mut a: ArrayQueue<[usize; 4]> = (0..4).collect();
+
+        assert_eq!(a.swap_remove_front(4), None);
+        assert_eq!(a.swap_remove_front(2), Some(2));
+        assert_eq!(a.as_slices(), (&[1, 0, 3][..], &[][..]));
+        assert_eq!(a.swap_remove_front(0), Some(1));
+        assert_eq!(a.as_slices(), (&[0, 3][..], &[][..]));
+    }
+
+    #[test]
+    fn pop_front_into() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_back(2).is_ok());
+        assert!(a.push_front(0).is_ok());
+
+        let mut xs = [42; 2];
+
+        assert_eq!(a.pop_front_into(&mut xs), 2);
+        assert_eq!(xs, [0, 1]);
+        assert_eq!(a.pop_front_into(&mut xs), 1);
+        assert_eq!(xs, [2, 1]);
+        assert_eq!(a.pop_front_into(&mut xs), 0);
+        assert!(a.is_empty());
+
+        let mut a: ArrayQueue<[Box<usize>; 2]> = (0..2).map(Box::new).collect();
+        let mut xs = [Box::new(42), Box::new(42), Box::new(42)];
+
+        assert_eq!(a.pop_front_into(&mut xs), 2);
+        assert_eq!(xs, [Box::new(0), Box::new(1), Box::new(42)]);
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn push_and_pop_across_edges() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_back(2).is_ok());
+
+        for i in 3..64 {
+            assert_eq!(a.pop_front(), Some(i - 2));
+            assert_eq!(a.len(), 1);
+            assert!(a.push_back(i).is_ok());
+            assert_eq!(a.len(), 2);
+        }
+    }
+
+    #[test]
+    fn is_empty() {
+        let a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+        assert!(a.is_empty());
+
+        let a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn is_full() {
+        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+        assert!(a.push_back(0).is_ok());
+        assert!(a.is_full());
+
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_back(0).is_ok());
+        assert!(a.is_full());
+    }
+
+    #[test]
+    fn capacity() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert_eq!(a.capacity(), 2);
+        assert_eq!(a.remaining_capacity(), 2);
+
+        assert!(a.push_back(0).is_ok());
+
+        assert_eq!(a.capacity(), 2);
+        assert_eq!(a.remaining_capacity(), 1);
+
+        assert_eq!(ArrayQueue::<[usize; 0]>::new().remaining_capacity(), 0);
+    }
+
+    #[test]
+    fn iterator() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_back(1).is_ok());
+
+        for (i, e) in a.into_iter().enumerate() {
+            assert_eq!(*e, i);
+        }
+    }
+
+    #[test]
+    fn iterator_across_edges() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(42).is_ok());
+        a.pop_front();
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_back(1).is_ok());
+
+        for (i, e) in a.into_iter().enumerate() {
+            assert_eq!(*e, i);
+        }
+    }
+
+    #[test]
+    fn iterate_forward_and_backward() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_back(1).is_ok());
+
+        let mut i = a.into_iter();
+
+        assert_eq!(i.next(), Some(&0));
+        assert_eq!(i.next_back(), Some(&1));
+        assert_eq!(i.next(), None);
+        assert_eq!(i.next_back(), None);
+    }
+
+    #[test]
+    fn iterate_forward_and_backward_mutablly() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_back(1).is_ok());
+
+        let mut i = (&mut a).into_iter();
+
+        assert_eq!(i.next(), Some(&mut 0));
+        assert_eq!(i.next_back(), Some(&mut 1));
+        assert_eq!(i.next(), None);
+        assert_eq!(i.next_back(), None);
+    }
+
+    #[test]
+    fn iterate_empty_queue() {
+        let a = ArrayQueue::<[usize; 0]>::new();
+
+        for _ in a.into_iter() {}
+    }
+
+    #[test]
+    fn iterator_mut() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_back(1).is_ok());
+
+        for (i, e) in (&mut a).into_iter().enumerate() {
+            assert_eq!(*e, i);
+            *e = 42;
+        }
+    }
+
+    #[test]
+    fn as_slices() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert_eq!(a.as_slices(), (&[][..], &[][..]));
+
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_back(2).is_ok());
+
+        assert_eq!(a.as_slices(), (&[1, 2][..], &[][..]));
+
+        assert!(a.push_front(0).is_ok());
+
+        assert_eq!(a.as_slices(), (&[0][..], &[1, 2][..]));
+    }
+
+    #[test]
+    fn as_mut_slices() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_front(0).is_ok());
+
+        {
+            let (l, m) = a.as_mut_slices();
+            l[0] = 42;
+            m[0] = 43;
+        }
+
+        assert_eq!(a.first(), Some(&42));
+        assert_eq!(a.last(), Some(&43));
+    }
+
+    #[test]
+    fn make_contiguous() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert!(a.make_contiguous().is_empty());
+
+        assert!(a.push_back(2).is_ok());
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_front(3).is_ok());
+        assert!(a.push_front(1).is_ok());
+
+        a.make_contiguous().sort();
+
+        assert_eq!(a.as_slices(), (&[0, 1, 2, 3][..], &[][..]));
+        assert_eq!(a.pop_front(), Some(0));
+        assert_eq!(a.pop_back(), Some(3));
+    }
+
+    #[test]
+    fn rotate_left() {
+        for n in 0..4 {
+            for l in n..4 {
+                let mut a: ArrayQueue<[usize; 3]> = ArrayQueue::new();
+                a.extend(0..l);
+                a.rotate_left(n);
+
+                let xs: Vec<usize> = a.into_iter().cloned().collect();
+                let mut ys: Vec<usize> = (0..l).collect();
+                ys.rotate_left(n);
+
+                assert_eq!(xs, ys);
+            }
+        }
+
+        let mut a: ArrayQueue<[usize; 0]> = ArrayQueue::new();
+        a.rotate_left(0);
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn rotate_right() {
+        for n in 0..4 {
+            for l in n..4 {
+                let mut a: ArrayQueue<[usize; 3]> = ArrayQueue::new();
+                a.extend(0..l);
+                a.rotate_right(n);
+
+                let xs: Vec<usize> = a.into_iter().cloned().collect();
+                let mut ys: Vec<usize> = (0..l).collect();
+                ys.rotate_right(n);
+
+                assert_eq!(xs, ys);
+            }
+        }
+
+        let mut a: ArrayQueue<[usize; 0]> = ArrayQueue::new();
+        a.rotate_right(0);
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotate_out_of_bounds() {
+        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        a.rotate_left(1);
+    }
+
+    #[test]
+    fn append() {
+        let mut a: ArrayQueue<[usize; 4]> = (0..2).collect();
+        let mut b: ArrayQueue<[usize; 4]> = (2..4).collect();
+
+        assert!(a.append(&mut b).is_ok());
+        assert_eq!(a, (0..4).collect());
+        assert!(b.is_empty());
+
+        let mut b: ArrayQueue<[usize; 4]> = (4..5).collect();
+
+        assert_eq!(a.append(&mut b), Err(CapacityError::new(())));
+        assert_eq!(a.len(), 4);
+        assert_eq!(b.len(), 1);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut a: ArrayQueue<[Box<usize>; 4]> = ArrayQueue::new();
+
+        assert!(a.push_back(Box::new(2)).is_ok());
+        assert!(a.push_back(Box::new(3)).is_ok());
+        assert!(a.push_front(Box::new(1)).is_ok());
+        assert!(a.push_front(Box::new(0)).is_ok());
+
+        let b = a.split_off(1);
+
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.first(), Some(&Box::new(0)));
+        assert_eq!(b.len(), 3);
+        assert_eq!(b.first(), Some(&Box::new(1)));
+        assert_eq!(b.last(), Some(&Box::new(3)));
+
+        assert!(a.split_off(1).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        ArrayQueue::<[usize; 2]>::new().split_off(1);
+    }
+
+    #[test]
+    fn resize_with() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert!(a.resize_with(3, || 42).is_ok());
+        assert_eq!(a, [42, 42, 42].iter().cloned().collect());
+        assert!(a.resize_with(1, || 0).is_ok());
+        assert_eq!(a, [42].iter().cloned().collect());
+        assert_eq!(a.resize_with(5, || 0), Err(CapacityError::new(())));
+        assert_eq!(a.len(), 1);
+    }
+
+    #[test]
+    fn fill() {
+        let mut a: ArrayQueue<[Box<usize>; 4]> = (0..3).map(Box::new).collect();
+
+        a.fill(&Box::new(42));
+
+        assert_eq!(a.len(), 3);
+
+        for x in &a {
+            assert_eq!(**x, 42);
+        }
+    }
+
+    #[test]
+    fn truncate_back() {
+        let mut a: ArrayQueue<[Box<usize>; 4]> = (0..4).map(Box::new).collect();
+
+        a.truncate_back(8);
+        assert_eq!(a.len(), 4);
+        a.truncate_back(2);
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.first(), Some(&Box::new(0)));
+        assert_eq!(a.last(), Some(&Box::new(1)));
+    }
+
+    #[test]
+    fn truncate_front() {
+        let mut a: ArrayQueue<[Box<usize>; 4]> = (0..4).map(Box::new).collect();
+
+        a.truncate_front(8);
+        assert_eq!(a.len(), 4);
+        a.truncate_front(2);
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.first(), Some(&Box::new(2)));
+        assert_eq!(a.last(), Some(&Box::new(3)));
+    }
+
+    #[test]
+    fn as_ptr() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
+
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_front(0).is_ok());
+
+        assert_eq!(unsafe { *a.as_ptr() }, 1);
+        assert_eq!(unsafe { *a.as_ptr().add(3) }, 0);
+
+        unsafe { *a.as_mut_ptr() = 42 };
+
+        assert_eq!(a.last(), Some(&42));
+    }
+
+    #[test]
+    fn clear() {
+        let mut a: ArrayQueue<[Box<usize>; 2]> = ArrayQueue::new();
+
+        assert!(a.push_back(Box::new(0)).is_ok());
+        assert!(a.push_front(Box::new(1)).is_ok());
+
+        a.clear();
+
+        assert!(a.is_empty());
+        assert!(a.push_back(Box::new(2)).is_ok());
+        assert_eq!(a.first(), Some(&Box::new(2)));
+    }
 
-impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Iterator
-    for ArrayQueueIterator<'a, A>
-{
-    type Item = &'a <A as Array>::Item;
+    #[test]
+    fn iterator_mut_rev() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.exhausted() {
-            return None;
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_back(2).is_ok());
+        assert!(a.push_front(0).is_ok());
+
+        for (i, x) in (&mut a).into_iter().rev().enumerate() {
+            *x += i;
         }
 
-        let x = &self.queue.array.as_ref()[self.queue.index(self.first)];
-        self.first += 1;
-        Some(x)
+        assert_eq!(a, [2, 2, 2].iter().cloned().collect());
     }
-}
 
-impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> DoubleEndedIterator
-    for ArrayQueueIterator<'a, A>
-{
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.exhausted() {
-            return None;
+    #[test]
+    fn iterator_len() {
+        let mut a: ArrayQueue<[usize; 4]> = (0..3).collect();
+
+        {
+            let mut i = a.into_iter();
+
+            assert_eq!(i.len(), 3);
+            i.next();
+            assert_eq!(i.size_hint(), (2, Some(2)));
+            i.next_back();
+            assert_eq!(i.len(), 1);
+            i.next();
+            assert_eq!(i.len(), 0);
         }
 
-        self.last -= 1;
-        let x = &self.queue.array.as_ref()[self.queue.index(self.last)];
-        Some(x)
-    }
-}
+        {
+            let mut i = (&mut a).into_iter();
 
-#[derive(Debug)]
-pub struct ArrayQueueMutIterator<
-    'a,
-    A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
-> {
-    queue: &'a mut ArrayQueue<A>,
-    first: usize,
-    last: usize,
-}
+            assert_eq!(i.len(), 3);
+            i.next_back();
+            assert_eq!(i.len(), 2);
+        }
 
-impl<'a, A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>>
-    ArrayQueueMutIterator<'a, A>
-{
-    fn exhausted(&self) -> bool {
-        self.first >= self.last
+        let mut i = a.drain();
+
+        assert_eq!(i.len(), 3);
+        i.next();
+        assert_eq!(i.len(), 2);
     }
-}
 
-impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Iterator
-    for ArrayQueueMutIterator<'a, A>
-{
-    type Item = &'a mut <A as Array>::Item;
+    #[test]
+    fn iterate_after_exhaustion() {
+        let mut a: ArrayQueue<[usize; 2]> = (0..1).collect();
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.exhausted() {
-            return None;
-        }
+        {
+            let mut i = a.into_iter();
 
-        let i = self.queue.index(self.first);
-        let x = &mut self.queue.array.as_mut()[i] as *mut <A as Array>::Item;
-        self.first += 1;
-        Some(unsafe { &mut *x })
-    }
-}
+            assert_eq!(i.next(), Some(&0));
 
-impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> DoubleEndedIterator
-    for ArrayQueueMutIterator<'a, A>
-{
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.exhausted() {
-            return None;
+            for _ in 0..2 {
+                assert_eq!(i.next(), None);
+                assert_eq!(i.next_back(), None);
+            }
+
+            assert_eq!(i.len(), 0);
         }
 
-        self.last -= 1;
-        let i = self.queue.index(self.last);
-        let x = &mut self.queue.array.as_mut()[i] as *mut <A as Array>::Item;
-        Some(unsafe { &mut *x })
-    }
-}
+        {
+            let mut i = (&mut a).into_iter();
 
-#[cfg(test)]
-mod test {
-    use super::*;
+            assert_eq!(i.next_back(), Some(&mut 0));
 
-    #[test]
-    fn new() {
-        ArrayQueue::<[usize; 1]>::new();
-        ArrayQueue::<[usize; 2]>::new();
+            for _ in 0..2 {
+                assert_eq!(i.next(), None);
+                assert_eq!(i.next_back(), None);
+            }
+
+            assert_eq!(i.len(), 0);
+        }
+
+        let mut i = a.drain();
+
+        assert_eq!(i.next(), Some(0));
+        assert_eq!(i.next(), None);
+        assert_eq!(i.next(), None);
     }
 
     #[test]
-    fn first_and_last() {
+    fn drain() {
         let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
 
-        assert_eq!(a.first(), None);
-        assert_eq!(a.first_mut(), None);
-        assert_eq!(a.last(), None);
-        assert_eq!(a.last_mut(), None);
+        assert!(a.push_back(42).is_ok());
+        a.pop_front();
+        assert!(a.push_back(0).is_ok());
+        assert!(a.push_back(1).is_ok());
 
-        assert!(a.push_back(&1).is_ok());
+        for (i, x) in a.drain().enumerate() {
+            assert_eq!(x, i);
+        }
 
-        assert_eq!(a.first(), Some(&1));
-        assert_eq!(a.first_mut(), Some(&mut 1));
-        assert_eq!(a.last(), Some(&1));
-        assert_eq!(a.last_mut(), Some(&mut 1));
+        assert!(a.is_empty());
+    }
 
-        assert!(a.push_back(&2).is_ok());
+    #[test]
+    fn drain_partially() {
+        let mut a: ArrayQueue<[Box<usize>; 4]> = ArrayQueue::new();
 
-        assert_eq!(a.first(), Some(&1));
-        assert_eq!(a.first_mut(), Some(&mut 1));
-        assert_eq!(a.last(), Some(&2));
-        assert_eq!(a.last_mut(), Some(&mut 2));
+        for i in 0..4 {
+            assert!(a.push_back(Box::new(i)).is_ok());
+        }
+
+        assert_eq!(a.drain().next(), Some(Box::new(0)));
+        assert!(a.is_empty());
     }
 
     #[test]
-    fn push_back() {
-        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+    fn extract_if() {
+        let mut a: ArrayQueue<[usize; 8]> = ArrayQueue::new();
 
-        assert_eq!(a.len(), 0);
-        assert!(a.push_back(&42).is_ok());
-        assert_eq!(a.len(), 1);
-        assert_eq!(a.push_back(&42), Err(CapacityError));
-        assert_eq!(a.len(), 1);
+        assert!(a.push_back(42).is_ok());
+        a.pop_front();
+        a.extend(0..8);
 
-        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        let xs: Vec<usize> = a.extract_if(|x| *x % 3 == 0).collect();
 
-        assert_eq!(a.len(), 0);
-        assert!(a.push_back(&42).is_ok());
-        assert_eq!(a.len(), 1);
-        assert!(a.push_back(&42).is_ok());
-        assert_eq!(a.len(), 2);
-        assert_eq!(a.push_back(&42), Err(CapacityError));
-        assert_eq!(a.len(), 2);
+        assert_eq!(xs, vec![0, 3, 6]);
+        assert_eq!(a, [1, 2, 4, 5, 7].iter().cloned().collect());
     }
 
     #[test]
-    fn push_front() {
-        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+    fn extract_if_zero_capacity() {
+        let mut a: ArrayQueue<[usize; 0]> = ArrayQueue::new();
 
-        assert_eq!(a.len(), 0);
-        assert!(a.push_front(&42).is_ok());
-        assert_eq!(a.len(), 1);
-        assert_eq!(a.push_front(&42), Err(CapacityError));
-        assert_eq!(a.len(), 1);
+        assert_eq!(a.extract_if(|_| true).count(), 0);
+        assert!(a.is_empty());
+    }
 
-        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+    #[test]
+    fn extract_if_partially() {
+        let mut a: ArrayQueue<[Box<usize>; 8]> = (0..6).map(Box::new).collect();
 
-        assert_eq!(a.len(), 0);
-        assert!(a.push_front(&1).is_ok());
-        assert_eq!(a.first(), Some(&1));
-        assert_eq!(a.last(), Some(&1));
-        assert_eq!(a.len(), 1);
-        assert!(a.push_front(&2).is_ok());
-        assert_eq!(a.first(), Some(&2));
-        assert_eq!(a.last(), Some(&1));
-        assert_eq!(a.len(), 2);
-        assert_eq!(a.push_front(&3), Err(CapacityError));
-        assert_eq!(a.len(), 2);
+        assert_eq!(a.extract_if(|x| **x % 2 == 1).next(), Some(Box::new(1)));
+
+        let xs: Vec<usize> = a.into_iter().map(|x| **x).collect();
+
+        assert_eq!(xs, vec![0, 2, 3, 4, 5]);
     }
 
     #[test]
-    fn pop_back() {
-        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
-
-        assert!(a.push_back(&42).is_ok());
+    fn retain() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
 
-        assert_eq!(a.pop_back(), Some(42));
-        assert_eq!(a.len(), 0);
+        assert!(a.push_back(42).is_ok());
+        assert!(a.push_back(42).is_ok());
+        a.pop_front();
+        a.pop_front();
 
-        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        for i in 0..4 {
+            assert!(a.push_back(i).is_ok());
+        }
 
-        assert!(a.push_back(&123).is_ok());
-        assert!(a.push_back(&42).is_ok());
+        a.retain(|x| x % 2 == 1);
 
-        assert_eq!(a.pop_back(), Some(42));
-        assert_eq!(a.first(), Some(&123));
-        assert_eq!(a.last(), Some(&123));
-        assert_eq!(a.len(), 1);
-        assert_eq!(a.pop_back(), Some(123));
-        assert_eq!(a.len(), 0);
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.first(), Some(&1));
+        assert_eq!(a.last(), Some(&3));
     }
 
     #[test]
-    fn pop_front() {
-        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
+    fn retain_mut() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
 
-        assert!(a.push_back(&42).is_ok());
+        for i in 0..4 {
+            assert!(a.push_back(i).is_ok());
+        }
 
-        assert_eq!(a.pop_front(), Some(42));
-        assert_eq!(a.len(), 0);
+        a.retain_mut(|x| {
+            *x += 1;
+            *x > 2
+        });
 
-        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.first(), Some(&3));
+        assert_eq!(a.last(), Some(&4));
+    }
 
-        assert!(a.push_back(&123).is_ok());
-        assert!(a.push_back(&42).is_ok());
+    #[test]
+    fn extend() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
 
-        assert_eq!(a.pop_front(), Some(123));
-        assert_eq!(a.first(), Some(&42));
-        assert_eq!(a.last(), Some(&42));
-        assert_eq!(a.len(), 1);
-        assert_eq!(a.pop_front(), Some(42));
-        assert_eq!(a.len(), 0);
+        assert!(a.push_back(0).is_ok());
+        a.extend(1..3);
+
+        assert_eq!(a.len(), 3);
+
+        for (i, x) in a.into_iter().enumerate() {
+            assert_eq!(*x, i);
+        }
     }
 
     #[test]
-    fn push_and_pop_across_edges() {
+    #[should_panic]
+    fn extend_over_capacity() {
         let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        a.extend(0..3);
+    }
 
-        assert!(a.push_back(&1).is_ok());
-        assert!(a.push_back(&2).is_ok());
+    #[test]
+    fn from_iter() {
+        let a: ArrayQueue<[usize; 4]> = (0..3).collect();
 
-        for i in 3..64 {
-            assert_eq!(a.pop_front(), Some(i - 2));
-            assert_eq!(a.len(), 1);
-            assert!(a.push_back(&i).is_ok());
-            assert_eq!(a.len(), 2);
+        assert_eq!(a.len(), 3);
+
+        for (i, x) in a.into_iter().enumerate() {
+            assert_eq!(*x, i);
         }
     }
 
     #[test]
-    fn is_empty() {
-        let a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
-        assert!(a.is_empty());
-
-        let a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
-        assert!(a.is_empty());
+    #[should_panic]
+    fn from_iter_over_capacity() {
+        let _: ArrayQueue<[usize; 2]> = (0..3).collect();
     }
 
     #[test]
-    fn is_full() {
-        let mut a: ArrayQueue<[usize; 1]> = ArrayQueue::new();
-        assert!(a.push_back(&0).is_ok());
-        assert!(a.is_full());
+    fn debug() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
 
-        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
-        assert!(a.push_back(&0).is_ok());
-        assert!(a.push_back(&0).is_ok());
-        assert!(a.is_full());
+        assert_eq!(format!("{:?}", a), "[]");
+
+        assert!(a.push_back(2).is_ok());
+        assert!(a.push_front(1).is_ok());
+
+        assert_eq!(format!("{:?}", a), "[1, 2]");
     }
 
     #[test]
-    fn iterator() {
-        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+    fn debug_iterators() {
+        let mut a: ArrayQueue<[usize; 4]> = (0..3).collect();
 
-        assert!(a.push_back(&0).is_ok());
-        assert!(a.push_back(&1).is_ok());
+        {
+            let mut i = a.into_iter();
+            i.next();
+            assert_eq!(format!("{:?}", i), "ArrayQueueIterator([1, 2])");
+        }
 
-        for (i, e) in a.into_iter().enumerate() {
-            assert_eq!(*e, i);
+        {
+            let mut i = (&mut a).into_iter();
+            i.next_back();
+            assert_eq!(format!("{:?}", i), "ArrayQueueMutIterator([0, 1])");
         }
+
+        assert_eq!(format!("{:?}", a.drain()), "ArrayQueueDrain([0, 1, 2])");
     }
 
     #[test]
-    fn iterator_across_edges() {
+    fn eq() {
         let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        let mut b: ArrayQueue<[usize; 2]> = ArrayQueue::new();
 
-        assert!(a.push_back(&42).is_ok());
-        a.pop_front();
-        assert!(a.push_back(&0).is_ok());
-        assert!(a.push_back(&1).is_ok());
+        assert_eq!(a, b);
 
-        for (i, e) in a.into_iter().enumerate() {
-            assert_eq!(*e, i);
-        }
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_back(2).is_ok());
+        assert!(b.push_back(2).is_ok());
+        assert!(b.push_front(1).is_ok());
+
+        assert_eq!(a, b);
+
+        b.pop_back();
+
+        assert_ne!(a, b);
+
+        assert!(b.push_back(3).is_ok());
+
+        assert_ne!(a, b);
     }
 
     #[test]
-    fn iterate_forward_and_backward() {
+    fn cmp() {
         let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        let mut b: ArrayQueue<[usize; 2]> = ArrayQueue::new();
 
-        assert!(a.push_back(&0).is_ok());
-        assert!(a.push_back(&1).is_ok());
+        assert_eq!(a.cmp(&b), Ordering::Equal);
 
-        let mut i = a.into_iter();
+        assert!(a.push_back(1).is_ok());
 
-        assert_eq!(i.next(), Some(&0));
-        assert_eq!(i.next_back(), Some(&1));
-        assert_eq!(i.next(), None);
-        assert_eq!(i.next_back(), None);
+        assert!(a > b);
+
+        assert!(b.push_back(1).is_ok());
+        assert!(b.push_back(0).is_ok());
+
+        assert!(a < b);
+
+        assert!(a.push_front(2).is_ok());
+
+        assert!(a > b);
+        assert_eq!(a.partial_cmp(&b), Some(Ordering::Greater));
     }
 
     #[test]
-    fn iterate_forward_and_backward_mutablly() {
+    fn hash() {
+        use std::collections::HashSet;
+
         let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+        let mut b: ArrayQueue<[usize; 2]> = ArrayQueue::new();
 
-        assert!(a.push_back(&0).is_ok());
-        assert!(a.push_back(&1).is_ok());
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_back(2).is_ok());
+        assert!(b.push_back(2).is_ok());
+        assert!(b.push_front(1).is_ok());
 
-        let mut i = (&mut a).into_iter();
+        let mut s = HashSet::new();
 
-        assert_eq!(i.next(), Some(&mut 0));
-        assert_eq!(i.next_back(), Some(&mut 1));
-        assert_eq!(i.next(), None);
-        assert_eq!(i.next_back(), None);
+        assert!(s.insert(a));
+        assert!(!s.insert(b));
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn iterate_empty_queue() {
-        let a = ArrayQueue::<[usize; 0]>::new();
+    fn serialize() {
+        let mut a: ArrayQueue<[usize; 4]> = ArrayQueue::new();
 
-        for _ in a.into_iter() {}
+        assert!(a.push_back(1).is_ok());
+        assert!(a.push_front(0).is_ok());
+
+        assert_eq!(serde_json::to_string(&a).unwrap(), "[0,1]");
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn iterator_mut() {
-        let mut a: ArrayQueue<[usize; 2]> = ArrayQueue::new();
+    fn deserialize() {
+        let a: ArrayQueue<[usize; 2]> = serde_json::from_str("[0,1]").unwrap();
+
+        assert_eq!(a.first(), Some(&0));
+        assert_eq!(a.last(), Some(&1));
 
-        assert!(a.push_back(&0).is_ok());
-        assert!(a.push_back(&1).is_ok());
+        assert!(serde_json::from_str::<ArrayQueue<[usize; 2]>>("[0,1,2]").is_err());
+    }
 
-        for (i, e) in (&mut a).into_iter().enumerate() {
-            assert_eq!(*e, i);
-            *e = 42;
-        }
+    #[test]
+    fn read() {
+        let mut a: ArrayQueue<[u8; 4]> = ArrayQueue::new();
+
+        assert!(a.try_extend_from_slice(b"xx").is_ok());
+        a.consume(2);
+        assert!(a.try_extend_from_slice(b"abcd").is_ok());
+
+        let mut buf = [0; 3];
+
+        assert_eq!(a.read(&mut buf).unwrap(), 3);
+        assert_eq!(&buf, b"abc");
+        assert_eq!(a.read(&mut buf).unwrap(), 1);
+        assert_eq!(&buf[..1], b"d");
+        assert_eq!(a.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn buf_read() {
+        let mut a: ArrayQueue<[u8; 8]> = ArrayQueue::new();
+
+        assert!(a.try_extend_from_slice(b"ab\ncd").is_ok());
+        assert_eq!(a.fill_buf().unwrap(), b"ab\ncd");
+
+        let mut s = String::new();
+
+        assert_eq!(a.read_line(&mut s).unwrap(), 3);
+        assert_eq!(s, "ab\n");
+
+        a.consume(42);
+
+        assert!(a.is_empty());
+        assert_eq!(a.fill_buf().unwrap(), b"");
+    }
+
+    #[test]
+    fn write() {
+        let mut a: ArrayQueue<[u8; 4]> = ArrayQueue::new();
+
+        assert_eq!(a.write(b"ab").unwrap(), 2);
+        assert!(write!(a, "{}", 1).is_ok());
+        assert_eq!(a.write(b"cd").unwrap(), 1);
+        assert_eq!(a.write(b"e").unwrap(), 0);
+        assert!(a.flush().is_ok());
+        assert_eq!(a, b"ab1c".iter().cloned().collect());
+
+        assert_eq!(
+            a.write_all(b"f").unwrap_err().kind(),
+            io::ErrorKind::WriteZero
+        );
     }
 
     #[test]
     fn reference_elements() {
         let mut a: ArrayQueue<[Box<usize>; 2]> = ArrayQueue::new();
-        assert!(a.push_back(&Box::new(42)).is_ok());
-        assert!(a.push_front(&Box::new(42)).is_ok());
+        assert!(a.push_back(Box::new(42)).is_ok());
+        assert!(a.push_front(Box::new(42)).is_ok());
     }
 
     #[test]
@@ -547,10 +2283,10 @@ mod test {
         let mut a: ArrayQueue<[Box<usize>; 32]> = ArrayQueue::new();
 
         for _ in 0..32 {
-            assert!(a.push_back(&Box::new(42)).is_ok());
+            assert!(a.push_back(Box::new(42)).is_ok());
         }
 
-        a.clone();
+        assert_eq!(a.clone().len(), 32);
     }
 
     static mut FOO_SUM: usize = 0;
@@ -573,14 +2309,14 @@ mod test {
         let mut a: ArrayQueue<[Foo; 32]> = ArrayQueue::new();
 
         for _ in 0..32 {
-            assert!(a.push_back(&Foo).is_ok());
+            assert!(a.push_back(Foo).is_ok());
         }
 
-        assert_eq!(unsafe { FOO_SUM }, 32); // drops of arguments `&Foo`
+        assert_eq!(unsafe { FOO_SUM }, 0);
 
         drop(a);
 
-        assert_eq!(unsafe { FOO_SUM }, 64); // drops of elements
+        assert_eq!(unsafe { FOO_SUM }, 32); // drops of elements
     }
 
     static mut BAR_SUM: usize = 0;
@@ -603,19 +2339,19 @@ mod test {
         let mut a: ArrayQueue<[Bar; 32]> = ArrayQueue::new();
 
         for _ in 0..32 {
-            assert!(a.push_back(&Bar).is_ok());
+            assert!(a.push_back(Bar).is_ok());
         }
 
-        assert_eq!(unsafe { BAR_SUM }, 32); // drops of arguments `&Bar`
+        assert_eq!(unsafe { BAR_SUM }, 0);
 
         for _ in 0..32 {
             assert!(a.pop_back().is_some());
         }
 
-        assert_eq!(unsafe { BAR_SUM }, 64); // drops of elements
+        assert_eq!(unsafe { BAR_SUM }, 32); // drops of elements
 
         drop(a);
 
-        assert_eq!(unsafe { BAR_SUM }, 64);
+        assert_eq!(unsafe { BAR_SUM }, 32);
     }
 }
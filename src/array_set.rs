@@ -0,0 +1,319 @@
+use std::fmt::{self, Debug, Formatter};
+use std::slice;
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+pub struct ArraySet<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    elements: ArrayQueue<A>,
+}
+
+impl<T: Eq, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> ArraySet<A> {
+    pub fn new() -> Self {
+        ArraySet {
+            elements: ArrayQueue::new(),
+        }
+    }
+
+    pub fn insert(&mut self, x: T) -> Result<bool, CapacityError<T>> {
+        if self.contains(&x) {
+            return Ok(false);
+        }
+
+        self.elements.push_back(x)?;
+        Ok(true)
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.elements.contains(x)
+    }
+
+    pub fn remove(&mut self, x: &T) -> bool {
+        match self.iter().position(|y| y == x) {
+            Some(i) => self.elements.swap_remove_back(i).is_some(),
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    pub fn iter<'a>(&'a self) -> ArraySetIterator<'a, T>
+    where
+        T: 'a,
+    {
+        self.into_iter()
+    }
+
+    pub fn union<'a, B: Array<Item = T> + AsRef<[T]> + AsMut<[T]>>(
+        &'a self,
+        other: &'a ArraySet<B>,
+    ) -> ArraySetUnion<'a, T>
+    where
+        T: 'a,
+    {
+        ArraySetUnion {
+            first: self.iter(),
+            second: other.difference(self),
+        }
+    }
+
+    pub fn intersection<'a, B: Array<Item = T> + AsRef<[T]> + AsMut<[T]>>(
+        &'a self,
+        other: &'a ArraySet<B>,
+    ) -> ArraySetIntersection<'a, T>
+    where
+        T: 'a,
+    {
+        ArraySetIntersection {
+            iterator: self.iter(),
+            other: other.iter(),
+        }
+    }
+
+    pub fn difference<'a, B: Array<Item = T> + AsRef<[T]> + AsMut<[T]>>(
+        &'a self,
+        other: &'a ArraySet<B>,
+    ) -> ArraySetDifference<'a, T>
+    where
+        T: 'a,
+    {
+        ArraySetDifference {
+            iterator: self.iter(),
+            other: other.iter(),
+        }
+    }
+
+    pub fn is_subset<B: Array<Item = T> + AsRef<[T]> + AsMut<[T]>>(
+        &self,
+        other: &ArraySet<B>,
+    ) -> bool {
+        self.iter().all(|x| other.contains(x))
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.elements.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+}
+
+impl<T: Clone, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Clone for ArraySet<A> {
+    fn clone(&self) -> Self {
+        ArraySet {
+            elements: self.elements.clone(),
+        }
+    }
+}
+
+impl<T: Debug, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Debug for ArraySet<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_set().entries(self).finish()
+    }
+}
+
+impl<T: Eq, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Default for ArraySet<A> {
+    fn default() -> Self {
+        ArraySet::new()
+    }
+}
+
+impl<'a, T: 'a, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> IntoIterator for &'a ArraySet<A> {
+    type Item = &'a T;
+    type IntoIter = ArraySetIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let (xs, ys) = self.elements.as_slices();
+
+        ArraySetIterator {
+            first: xs.iter(),
+            second: ys.iter(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ArraySetIterator<'a, T: 'a> {
+    first: slice::Iter<'a, T>,
+    second: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Clone for ArraySetIterator<'a, T> {
+    fn clone(&self) -> Self {
+        ArraySetIterator {
+            first: self.first.clone(),
+            second: self.second.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for ArraySetIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.first.len() + self.second.len();
+        (n, Some(n))
+    }
+}
+
+#[derive(Debug)]
+pub struct ArraySetUnion<'a, T: 'a> {
+    first: ArraySetIterator<'a, T>,
+    second: ArraySetDifference<'a, T>,
+}
+
+impl<'a, T: Eq> Iterator for ArraySetUnion<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+}
+
+#[derive(Debug)]
+pub struct ArraySetIntersection<'a, T: 'a> {
+    iterator: ArraySetIterator<'a, T>,
+    other: ArraySetIterator<'a, T>,
+}
+
+impl<'a, T: Eq> Iterator for ArraySetIntersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = &self.other;
+        self.iterator.find(|x| other.clone().any(|y| y == *x))
+    }
+}
+
+#[derive(Debug)]
+pub struct ArraySetDifference<'a, T: 'a> {
+    iterator: ArraySetIterator<'a, T>,
+    other: ArraySetIterator<'a, T>,
+}
+
+impl<'a, T: Eq> Iterator for ArraySetDifference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = &self.other;
+        self.iterator.find(|x| !other.clone().any(|y| y == *x))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArraySet::<[usize; 0]>::new();
+        ArraySet::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn insert() {
+        let mut s: ArraySet<[usize; 2]> = ArraySet::new();
+
+        assert_eq!(s.insert(1), Ok(true));
+        assert_eq!(s.insert(1), Ok(false));
+        assert_eq!(s.insert(2), Ok(true));
+        assert_eq!(s.insert(2), Ok(false));
+        assert_eq!(s.insert(3), Err(CapacityError::new(3)));
+        assert_eq!(s.len(), 2);
+    }
+
+    #[test]
+    fn remove() {
+        let mut s: ArraySet<[usize; 8]> = ArraySet::new();
+
+        for x in 1..4 {
+            assert!(s.insert(x).is_ok());
+        }
+
+        assert!(s.remove(&2));
+        assert!(!s.remove(&2));
+        assert!(!s.contains(&2));
+        assert!(s.contains(&1));
+        assert!(s.contains(&3));
+        assert_eq!(s.len(), 2);
+    }
+
+    #[test]
+    fn union() {
+        let mut s: ArraySet<[usize; 8]> = ArraySet::new();
+        let mut t: ArraySet<[usize; 8]> = ArraySet::new();
+
+        for x in 1..4 {
+            assert!(s.insert(x).is_ok());
+            assert!(t.insert(x + 1).is_ok());
+        }
+
+        let mut xs: Vec<usize> = s.union(&t).cloned().collect();
+        xs.sort();
+
+        assert_eq!(xs, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn intersection() {
+        let mut s: ArraySet<[usize; 8]> = ArraySet::new();
+        let mut t: ArraySet<[usize; 8]> = ArraySet::new();
+
+        for x in 1..4 {
+            assert!(s.insert(x).is_ok());
+            assert!(t.insert(x + 1).is_ok());
+        }
+
+        let mut xs: Vec<usize> = s.intersection(&t).cloned().collect();
+        xs.sort();
+
+        assert_eq!(xs, vec![2, 3]);
+    }
+
+    #[test]
+    fn difference() {
+        let mut s: ArraySet<[usize; 8]> = ArraySet::new();
+        let mut t: ArraySet<[usize; 8]> = ArraySet::new();
+
+        for x in 1..4 {
+            assert!(s.insert(x).is_ok());
+            assert!(t.insert(x + 1).is_ok());
+        }
+
+        let xs: Vec<usize> = s.difference(&t).cloned().collect();
+
+        assert_eq!(xs, vec![1]);
+    }
+
+    #[test]
+    fn is_subset() {
+        let mut s: ArraySet<[usize; 8]> = ArraySet::new();
+        let mut t: ArraySet<[usize; 8]> = ArraySet::new();
+
+        assert!(s.is_subset(&t));
+        assert!(t.insert(1).is_ok());
+        assert!(s.is_subset(&t));
+        assert!(s.insert(2).is_ok());
+        assert!(!s.is_subset(&t));
+        assert!(t.insert(2).is_ok());
+        assert!(s.is_subset(&t));
+        assert!(!t.is_subset(&s));
+    }
+}
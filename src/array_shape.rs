@@ -0,0 +1,34 @@
+use std::array;
+
+use arrayvec::Array;
+
+/// An array type which can be built element by element and has counterparts
+/// of the same length for other element types.
+///
+/// Containers which keep extra states per slot use it to allocate arrays of
+/// those states next to their elements.
+pub trait ArrayShape:
+    Array + AsRef<[<Self as Array>::Item]> + AsMut<[<Self as Array>::Item]>
+{
+    /// An array of the same length with elements of `U`.
+    type With<U>: AsRef<[U]> + AsMut<[U]>;
+
+    fn from_fn<F: FnMut(usize) -> <Self as Array>::Item>(f: F) -> Self;
+
+    fn with_fn<U, F: FnMut(usize) -> U>(f: F) -> Self::With<U>;
+}
+
+impl<T, const N: usize> ArrayShape for [T; N]
+where
+    [T; N]: Array<Item = T>,
+{
+    type With<U> = [U; N];
+
+    fn from_fn<F: FnMut(usize) -> T>(f: F) -> Self {
+        array::from_fn(f)
+    }
+
+    fn with_fn<U, F: FnMut(usize) -> U>(f: F) -> [U; N] {
+        array::from_fn(f)
+    }
+}
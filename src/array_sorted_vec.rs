@@ -0,0 +1,194 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Bound, RangeBounds};
+use std::slice;
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+pub struct ArraySortedVec<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    elements: ArrayQueue<A>,
+}
+
+impl<T: Ord, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> ArraySortedVec<A> {
+    pub fn new() -> Self {
+        ArraySortedVec {
+            elements: ArrayQueue::new(),
+        }
+    }
+
+    pub fn insert(&mut self, x: T) -> Result<usize, CapacityError<T>> {
+        let i = self.as_slice().partition_point(|y| *y <= x);
+
+        self.elements.insert(i, x)?;
+        self.elements.make_contiguous();
+
+        Ok(i)
+    }
+
+    pub fn remove(&mut self, x: &T) -> bool {
+        match self.as_slice().binary_search(x) {
+            Ok(i) => self.elements.remove(i).is_some(),
+            Err(_) => false,
+        }
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.as_slice().binary_search(x).is_ok()
+    }
+
+    pub fn range<R: RangeBounds<T>>(&self, r: R) -> &[T] {
+        let xs = self.as_slice();
+
+        let i = match r.start_bound() {
+            Bound::Included(x) => xs.partition_point(|y| y < x),
+            Bound::Excluded(x) => xs.partition_point(|y| y <= x),
+            Bound::Unbounded => 0,
+        };
+
+        let j = match r.end_bound() {
+            Bound::Included(x) => xs.partition_point(|y| y <= x),
+            Bound::Excluded(x) => xs.partition_point(|y| y < x),
+            Bound::Unbounded => xs.len(),
+        };
+
+        &xs[i..j.max(i)]
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.elements.last()
+    }
+
+    pub fn pop_first(&mut self) -> Option<T> {
+        self.elements.pop_front()
+    }
+
+    pub fn pop_last(&mut self) -> Option<T> {
+        self.elements.pop_back()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        self.elements.as_contiguous_slice()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.elements.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+}
+
+impl<T: Clone, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Clone for ArraySortedVec<A> {
+    fn clone(&self) -> Self {
+        ArraySortedVec {
+            elements: self.elements.clone(),
+        }
+    }
+}
+
+impl<T: Debug, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Debug for ArraySortedVec<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.elements, f)
+    }
+}
+
+impl<T: Ord, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Default for ArraySortedVec<A> {
+    fn default() -> Self {
+        ArraySortedVec::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArraySortedVec::<[usize; 0]>::new();
+        ArraySortedVec::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn insert() {
+        let mut v: ArraySortedVec<[usize; 4]> = ArraySortedVec::new();
+
+        assert_eq!(v.insert(3), Ok(0));
+        assert_eq!(v.insert(1), Ok(0));
+        assert_eq!(v.insert(2), Ok(1));
+        assert_eq!(v.insert(2), Ok(2));
+        assert_eq!(v.insert(0), Err(CapacityError::new(0)));
+        assert_eq!(v.as_slice(), &[1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn remove() {
+        let mut v: ArraySortedVec<[usize; 8]> = ArraySortedVec::new();
+
+        for &x in &[3, 1, 2, 2] {
+            assert!(v.insert(x).is_ok());
+        }
+
+        assert!(v.remove(&2));
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+        assert!(v.remove(&1));
+        assert!(!v.remove(&1));
+        assert!(v.contains(&3));
+        assert!(!v.contains(&1));
+        assert_eq!(v.as_slice(), &[2, 3]);
+    }
+
+    #[test]
+    fn range() {
+        let mut v: ArraySortedVec<[usize; 8]> = ArraySortedVec::new();
+
+        for &x in &[5, 1, 3, 7, 3, 9] {
+            assert!(v.insert(x).is_ok());
+        }
+
+        assert_eq!(v.range(..), &[1, 3, 3, 5, 7, 9]);
+        assert_eq!(v.range(3..7), &[3, 3, 5]);
+        assert_eq!(v.range(3..=7), &[3, 3, 5, 7]);
+        assert_eq!(v.range((Bound::Excluded(3), Bound::Unbounded)), &[5, 7, 9]);
+        assert_eq!(v.range(..2), &[1]);
+        assert!(v.range(4..5).is_empty());
+    }
+
+    #[test]
+    fn pop() {
+        let mut v: ArraySortedVec<[usize; 8]> = ArraySortedVec::new();
+
+        for &x in &[2, 3, 1] {
+            assert!(v.insert(x).is_ok());
+        }
+
+        assert_eq!(v.first(), Some(&1));
+        assert_eq!(v.last(), Some(&3));
+        assert_eq!(v.pop_first(), Some(1));
+        assert_eq!(v.pop_last(), Some(3));
+        assert!(v.insert(0).is_ok());
+        assert_eq!(v.iter().collect::<Vec<_>>(), vec![&0, &2]);
+    }
+}
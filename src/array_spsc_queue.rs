@@ -0,0 +1,279 @@
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug, Formatter};
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use arrayvec::Array;
+
+use super::error::CapacityError;
+
+/// A lock-free single-producer single-consumer queue.
+///
+/// Call `split` to get a `Producer` and a `Consumer` which can be moved to
+/// different threads or to an interrupt handler and a main loop.
+pub struct ArraySpscQueue<A: Array> {
+    array: UnsafeCell<MaybeUninit<A>>,
+    // Both indices run over `0..2 * capacity` so that a full queue can be told
+    // apart from an empty one without wasting a slot.
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+impl<A: Array> ArraySpscQueue<A> {
+    pub fn new() -> Self {
+        ArraySpscQueue {
+            array: UnsafeCell::new(MaybeUninit::uninit()),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn split(&mut self) -> (Producer<'_, A>, Consumer<'_, A>) {
+        (Producer { queue: self }, Consumer { queue: self })
+    }
+
+    pub fn len(&self) -> usize {
+        self.distance(
+            self.head.load(Ordering::Acquire),
+            self.tail.load(Ordering::Acquire),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            tail + 2 * self.capacity() - head
+        }
+    }
+
+    fn increment(&self, i: usize) -> usize {
+        (i + 1) % (2 * self.capacity())
+    }
+
+    fn pointer(&self, i: usize) -> *mut <A as Array>::Item {
+        unsafe {
+            ((*self.array.get()).as_mut_ptr() as *mut <A as Array>::Item).add(i % self.capacity())
+        }
+    }
+
+    fn push(&self, x: <A as Array>::Item) -> Result<(), CapacityError<<A as Array>::Item>> {
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        if self.distance(self.head.load(Ordering::Acquire), tail) == self.capacity() {
+            return Err(CapacityError::new(x));
+        }
+
+        unsafe { ptr::write(self.pointer(tail), x) };
+        self.tail.store(self.increment(tail), Ordering::Release);
+
+        Ok(())
+    }
+
+    fn pop(&self) -> Option<<A as Array>::Item> {
+        let head = self.head.load(Ordering::Relaxed);
+
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let x = unsafe { ptr::read(self.pointer(head)) };
+        self.head.store(self.increment(head), Ordering::Release);
+
+        Some(x)
+    }
+}
+
+impl<A: Array> Debug for ArraySpscQueue<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ArraySpscQueue")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<A: Array> Default for ArraySpscQueue<A> {
+    fn default() -> Self {
+        ArraySpscQueue::new()
+    }
+}
+
+impl<A: Array> Drop for ArraySpscQueue<A> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+pub struct Producer<'a, A: 'a + Array> {
+    queue: &'a ArraySpscQueue<A>,
+}
+
+unsafe impl<'a, A: Array> Send for Producer<'a, A> where <A as Array>::Item: Send {}
+
+impl<'a, A: Array> Producer<'a, A> {
+    pub fn push(&mut self, x: <A as Array>::Item) -> Result<(), CapacityError<<A as Array>::Item>> {
+        self.queue.push(x)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.queue.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+}
+
+impl<'a, A: Array> Debug for Producer<'a, A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Producer").field(self.queue).finish()
+    }
+}
+
+pub struct Consumer<'a, A: 'a + Array> {
+    queue: &'a ArraySpscQueue<A>,
+}
+
+unsafe impl<'a, A: Array> Send for Consumer<'a, A> where <A as Array>::Item: Send {}
+
+impl<'a, A: Array> Consumer<'a, A> {
+    pub fn pop(&mut self) -> Option<<A as Array>::Item> {
+        self.queue.pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.queue.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+}
+
+impl<'a, A: Array> Debug for Consumer<'a, A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Consumer").field(self.queue).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::thread;
+
+    #[test]
+    fn new() {
+        ArraySpscQueue::<[usize; 0]>::new();
+        ArraySpscQueue::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut q: ArraySpscQueue<[usize; 2]> = ArraySpscQueue::new();
+
+        {
+            let (mut p, mut c) = q.split();
+
+            assert_eq!(c.pop(), None);
+            assert!(p.push(1).is_ok());
+            assert!(p.push(2).is_ok());
+            assert_eq!(p.push(3), Err(CapacityError::new(3)));
+            assert!(p.is_full());
+            assert_eq!(c.pop(), Some(1));
+            assert!(p.push(3).is_ok());
+            assert_eq!(c.pop(), Some(2));
+            assert_eq!(c.pop(), Some(3));
+            assert_eq!(c.pop(), None);
+        }
+
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut q: ArraySpscQueue<[usize; 0]> = ArraySpscQueue::new();
+        let (mut p, mut c) = q.split();
+
+        assert_eq!(p.push(1), Err(CapacityError::new(1)));
+        assert_eq!(c.pop(), None);
+    }
+
+    #[test]
+    fn drop_remaining_elements() {
+        let mut q: ArraySpscQueue<[Box<usize>; 4]> = ArraySpscQueue::new();
+
+        {
+            let (mut p, _) = q.split();
+
+            assert!(p.push(Box::new(1)).is_ok());
+            assert!(p.push(Box::new(2)).is_ok());
+        }
+
+        assert_eq!(q.len(), 2);
+    }
+
+    #[test]
+    fn threads() {
+        const N: usize = 10000;
+
+        let mut q: ArraySpscQueue<[usize; 16]> = ArraySpscQueue::new();
+        let (mut p, mut c) = q.split();
+
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..N {
+                    let mut x = i;
+
+                    while let Err(e) = p.push(x) {
+                        x = e.element();
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            s.spawn(move || {
+                let mut i = 0;
+
+                while i < N {
+                    if let Some(x) = c.pop() {
+                        assert_eq!(x, i);
+                        i += 1;
+                    } else {
+                        thread::yield_now();
+                    }
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,199 @@
+use std::fmt::{self, Debug, Formatter};
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::slice;
+
+use arrayvec::Array;
+
+use super::error::CapacityError;
+
+pub struct ArrayStack<A: Array> {
+    array: MaybeUninit<A>,
+    length: usize,
+}
+
+impl<A: Array> ArrayStack<A> {
+    pub fn new() -> Self {
+        ArrayStack {
+            array: MaybeUninit::uninit(),
+            length: 0,
+        }
+    }
+
+    pub fn push(&mut self, x: <A as Array>::Item) -> Result<(), CapacityError<<A as Array>::Item>> {
+        if self.is_full() {
+            return Err(CapacityError::new(x));
+        }
+
+        let i = self.length;
+        unsafe { ptr::write(self.as_mut_ptr().add(i), x) };
+        self.length += 1;
+
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<<A as Array>::Item> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.length -= 1;
+        Some(unsafe { ptr::read(self.as_ptr().add(self.length)) })
+    }
+
+    pub fn peek(&self) -> Option<&<A as Array>::Item> {
+        self.as_slice().last()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut <A as Array>::Item> {
+        self.as_mut_slice().last_mut()
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    pub fn as_slice(&self) -> &[<A as Array>::Item] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.length) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [<A as Array>::Item] {
+        let l = self.length;
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), l) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    fn as_ptr(&self) -> *const <A as Array>::Item {
+        self.array.as_ptr() as *const <A as Array>::Item
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut <A as Array>::Item {
+        self.array.as_mut_ptr() as *mut <A as Array>::Item
+    }
+}
+
+impl<A: Array> Clone for ArrayStack<A>
+where
+    <A as Array>::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut s = Self::new();
+
+        for x in self.as_slice() {
+            s.push(x.clone()).ok().unwrap();
+        }
+
+        s
+    }
+}
+
+impl<A: Array> Debug for ArrayStack<A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<A: Array> Default for ArrayStack<A> {
+    fn default() -> Self {
+        ArrayStack::new()
+    }
+}
+
+impl<A: Array> Drop for ArrayStack<A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn new() {
+        ArrayStack::<[usize; 0]>::new();
+        ArrayStack::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut s: ArrayStack<[usize; 2]> = ArrayStack::new();
+
+        assert!(s.push(1).is_ok());
+        assert!(s.push(2).is_ok());
+        assert_eq!(s.push(3), Err(CapacityError::new(3)));
+        assert!(s.is_full());
+        assert_eq!(s.as_slice(), &[1, 2]);
+        assert_eq!(s.pop(), Some(2));
+        assert_eq!(s.pop(), Some(1));
+        assert_eq!(s.pop(), None);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn peek() {
+        let mut s: ArrayStack<[usize; 2]> = ArrayStack::new();
+
+        assert_eq!(s.peek(), None);
+        assert!(s.push(1).is_ok());
+        assert_eq!(s.peek(), Some(&1));
+
+        *s.peek_mut().unwrap() = 2;
+
+        assert_eq!(s.peek(), Some(&2));
+        assert_eq!(s.len(), 1);
+    }
+
+    #[test]
+    fn non_copy_elements() {
+        let mut s: ArrayStack<[Box<usize>; 2]> = ArrayStack::new();
+
+        assert!(s.push(Box::new(1)).is_ok());
+        assert_eq!(s.clone().pop(), Some(Box::new(1)));
+        assert_eq!(s.pop(), Some(Box::new(1)));
+    }
+
+    #[test]
+    fn drop() {
+        let counter = Rc::new(Cell::new(0));
+
+        struct Foo(Rc<Cell<usize>>);
+
+        impl Drop for Foo {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        {
+            let mut s: ArrayStack<[Foo; 4]> = ArrayStack::new();
+
+            for _ in 0..3 {
+                assert!(s.push(Foo(counter.clone())).is_ok());
+            }
+        }
+
+        assert_eq!(counter.get(), 3);
+    }
+}
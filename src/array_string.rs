@@ -0,0 +1,237 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::Deref;
+use std::str;
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+#[macro_export]
+macro_rules! array_format {
+    ($n:expr, $($arg:tt)*) => {{
+        let mut s = $crate::ArrayString::<[u8; $n]>::new();
+
+        match ::std::fmt::Write::write_fmt(&mut s, format_args!($($arg)*)) {
+            Ok(()) => Ok(s),
+            Err(_) => Err($crate::CapacityError::new(())),
+        }
+    }};
+}
+
+pub struct ArrayString<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> {
+    bytes: ArrayQueue<A>,
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> ArrayString<A> {
+    pub fn new() -> Self {
+        ArrayString {
+            bytes: ArrayQueue::new(),
+        }
+    }
+
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        let mut buf = [0; 4];
+
+        self.push_str(c.encode_utf8(&mut buf))
+            .map_err(|_| CapacityError::new(c))
+    }
+
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        self.bytes.try_extend_from_slice(s.as_bytes())
+    }
+
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.bytes.truncate_back(self.len() - c.len_utf8());
+        Some(c)
+    }
+
+    pub fn truncate(&mut self, n: usize) {
+        if n < self.len() {
+            assert!(self.as_str().is_char_boundary(n), "not a char boundary");
+            self.bytes.truncate_back(n);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(self.bytes.as_contiguous_slice()) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.bytes.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    pub fn remaining_capacity(&self) -> usize {
+        self.bytes.remaining_capacity()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Clone for ArrayString<A> {
+    fn clone(&self) -> Self {
+        ArrayString {
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Debug for ArrayString<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Default for ArrayString<A> {
+    fn default() -> Self {
+        ArrayString::new()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Deref for ArrayString<A> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Display for ArrayString<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> fmt::Write for ArrayString<A> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> PartialEq for ArrayString<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Eq for ArrayString<A> {}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> PartialEq<str> for ArrayString<A> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        ArrayString::<[u8; 0]>::new();
+        ArrayString::<[u8; 4]>::new();
+    }
+
+    #[test]
+    fn push() {
+        let mut s: ArrayString<[u8; 4]> = ArrayString::new();
+
+        assert!(s.push('a').is_ok());
+        assert!(s.push('\u{3042}').is_ok());
+        assert_eq!(s.push('b'), Err(CapacityError::new('b')));
+        assert_eq!(s.as_str(), "a\u{3042}");
+    }
+
+    #[test]
+    fn push_str() {
+        let mut s: ArrayString<[u8; 4]> = ArrayString::new();
+
+        assert!(s.push_str("ab").is_ok());
+        assert!(s.push_str("cd").is_ok());
+        assert_eq!(s.push_str("e"), Err(CapacityError::new(())));
+        assert_eq!(&s, "abcd");
+        assert!(s.is_full());
+    }
+
+    #[test]
+    fn pop() {
+        let mut s: ArrayString<[u8; 4]> = ArrayString::new();
+
+        assert!(s.push_str("a\u{3042}").is_ok());
+        assert_eq!(s.pop(), Some('\u{3042}'));
+        assert_eq!(s.pop(), Some('a'));
+        assert_eq!(s.pop(), None);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut s: ArrayString<[u8; 8]> = ArrayString::new();
+
+        assert!(s.push_str("a\u{3042}b").is_ok());
+
+        s.truncate(8);
+        assert_eq!(&s, "a\u{3042}b");
+        s.truncate(4);
+        assert_eq!(&s, "a\u{3042}");
+        s.truncate(1);
+        assert_eq!(&s, "a");
+    }
+
+    #[test]
+    #[should_panic]
+    fn truncate_inside_char() {
+        let mut s: ArrayString<[u8; 4]> = ArrayString::new();
+
+        assert!(s.push('\u{3042}').is_ok());
+
+        s.truncate(1);
+    }
+
+    #[test]
+    fn write() {
+        use std::fmt::Write;
+
+        let mut s: ArrayString<[u8; 4]> = ArrayString::new();
+
+        assert!(write!(s, "{}{}", 1, 2).is_ok());
+        assert!(s.write_char('3').is_ok());
+        assert!(write!(s, "{}", 45).is_err());
+        assert_eq!(&s, "123");
+    }
+
+    #[test]
+    fn array_format() {
+        let s = array_format!(4, "{}-{}", 1, 2).unwrap();
+
+        assert_eq!(&s, "1-2");
+        assert_eq!(s.capacity(), 4);
+        assert!(array_format!(2, "{}", 123).is_err());
+    }
+
+    #[test]
+    fn format() {
+        let mut s: ArrayString<[u8; 4]> = ArrayString::new();
+
+        assert!(s.push_str("ab").is_ok());
+
+        assert_eq!(format!("{}", s), "ab");
+        assert_eq!(format!("{:?}", s), "\"ab\"");
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.remaining_capacity(), 2);
+    }
+}
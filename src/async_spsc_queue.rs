@@ -0,0 +1,290 @@
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+use arrayvec::Array;
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use super::array_spsc_queue::{ArraySpscQueue, Consumer, Producer};
+use super::error::CapacityError;
+
+/// An asynchronous single-producer single-consumer queue.
+///
+/// Its producer half is a `Sink` and its consumer half is a `Stream` which
+/// ends once the producer is closed or dropped.
+pub struct AsyncSpscQueue<A: Array> {
+    queue: ArraySpscQueue<A>,
+    producer_waker: Mutex<Option<Waker>>,
+    consumer_waker: Mutex<Option<Waker>>,
+    closed: AtomicBool,
+}
+
+impl<A: Array> AsyncSpscQueue<A> {
+    pub fn new() -> Self {
+        AsyncSpscQueue {
+            queue: ArraySpscQueue::new(),
+            producer_waker: Mutex::new(None),
+            consumer_waker: Mutex::new(None),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn split(&mut self) -> (AsyncProducer<'_, A>, AsyncConsumer<'_, A>) {
+        self.closed.store(false, Ordering::Release);
+
+        let (producer, consumer) = self.queue.split();
+        let (producer_waker, consumer_waker, closed) =
+            (&self.producer_waker, &self.consumer_waker, &self.closed);
+
+        (
+            AsyncProducer {
+                producer,
+                producer_waker,
+                consumer_waker,
+                closed,
+            },
+            AsyncConsumer {
+                consumer,
+                producer_waker,
+                consumer_waker,
+                closed,
+            },
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+}
+
+impl<A: Array> Debug for AsyncSpscQueue<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("AsyncSpscQueue").field(&self.queue).finish()
+    }
+}
+
+impl<A: Array> Default for AsyncSpscQueue<A> {
+    fn default() -> Self {
+        AsyncSpscQueue::new()
+    }
+}
+
+fn register(waker: &Mutex<Option<Waker>>, cx: &Context) {
+    *waker.lock().unwrap() = Some(cx.waker().clone());
+}
+
+fn wake(waker: &Mutex<Option<Waker>>) {
+    if let Some(w) = waker.lock().unwrap().take() {
+        w.wake();
+    }
+}
+
+pub struct AsyncProducer<'a, A: 'a + Array> {
+    producer: Producer<'a, A>,
+    producer_waker: &'a Mutex<Option<Waker>>,
+    consumer_waker: &'a Mutex<Option<Waker>>,
+    closed: &'a AtomicBool,
+}
+
+impl<'a, A: Array> AsyncProducer<'a, A> {
+    pub fn try_push(
+        &mut self,
+        x: <A as Array>::Item,
+    ) -> Result<(), CapacityError<<A as Array>::Item>> {
+        self.producer.push(x)?;
+        wake(self.consumer_waker);
+        Ok(())
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        wake(self.consumer_waker);
+    }
+}
+
+impl<'a, A: Array> Debug for AsyncProducer<'a, A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("AsyncProducer")
+            .field(&self.producer)
+            .finish()
+    }
+}
+
+impl<'a, A: Array> Sink<<A as Array>::Item> for AsyncProducer<'a, A> {
+    type Error = CapacityError<<A as Array>::Item>;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        if !self.producer.is_full() {
+            return Poll::Ready(Ok(()));
+        }
+
+        register(self.producer_waker, cx);
+
+        // The consumer might have popped an element before the waker was
+        // registered.
+        if self.producer.is_full() {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, x: <A as Array>::Item) -> Result<(), Self::Error> {
+        self.try_push(x)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.close();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a, A: Array> Drop for AsyncProducer<'a, A> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+pub struct AsyncConsumer<'a, A: 'a + Array> {
+    consumer: Consumer<'a, A>,
+    producer_waker: &'a Mutex<Option<Waker>>,
+    consumer_waker: &'a Mutex<Option<Waker>>,
+    closed: &'a AtomicBool,
+}
+
+impl<'a, A: Array> AsyncConsumer<'a, A> {
+    pub fn try_pop(&mut self) -> Option<<A as Array>::Item> {
+        let x = self.consumer.pop()?;
+        wake(self.producer_waker);
+        Some(x)
+    }
+}
+
+impl<'a, A: Array> Debug for AsyncConsumer<'a, A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("AsyncConsumer")
+            .field(&self.consumer)
+            .finish()
+    }
+}
+
+impl<'a, A: Array> Stream for AsyncConsumer<'a, A> {
+    type Item = <A as Array>::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        if let Some(x) = self.try_pop() {
+            return Poll::Ready(Some(x));
+        }
+
+        register(self.consumer_waker, cx);
+
+        // Check the queue again in case the producer pushed an element or
+        // closed before the waker was registered.
+        let closed = self.closed.load(Ordering::Acquire);
+
+        match self.try_pop() {
+            Some(x) => Poll::Ready(Some(x)),
+            None if closed => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.consumer.len(), None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn new() {
+        AsyncSpscQueue::<[usize; 0]>::new();
+        AsyncSpscQueue::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn send_and_receive() {
+        let mut q: AsyncSpscQueue<[usize; 2]> = AsyncSpscQueue::new();
+        let (mut p, mut c) = q.split();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert_eq!(Pin::new(&mut c).poll_next(&mut cx), Poll::Pending);
+
+        for x in 0..2 {
+            assert_eq!(Pin::new(&mut p).poll_ready(&mut cx), Poll::Ready(Ok(())));
+            assert!(Pin::new(&mut p).start_send(x).is_ok());
+        }
+
+        assert_eq!(Pin::new(&mut p).poll_ready(&mut cx), Poll::Pending);
+        assert_eq!(Pin::new(&mut c).poll_next(&mut cx), Poll::Ready(Some(0)));
+        assert_eq!(Pin::new(&mut p).poll_ready(&mut cx), Poll::Ready(Ok(())));
+        assert_eq!(Pin::new(&mut p).poll_close(&mut cx), Poll::Ready(Ok(())));
+        assert_eq!(Pin::new(&mut c).poll_next(&mut cx), Poll::Ready(Some(1)));
+        assert_eq!(Pin::new(&mut c).poll_next(&mut cx), Poll::Ready(None));
+    }
+
+    #[test]
+    fn wake_consumer() {
+        let mut q: AsyncSpscQueue<[usize; 2]> = AsyncSpscQueue::new();
+        let (mut p, mut c) = q.split();
+        let w = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(w.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(Pin::new(&mut c).poll_next(&mut cx), Poll::Pending);
+        assert!(p.try_push(1).is_ok());
+        assert_eq!(w.0.load(Ordering::SeqCst), 1);
+
+        assert_eq!(Pin::new(&mut c).poll_next(&mut cx), Poll::Ready(Some(1)));
+        assert_eq!(Pin::new(&mut c).poll_next(&mut cx), Poll::Pending);
+
+        drop(p);
+
+        assert_eq!(w.0.load(Ordering::SeqCst), 2);
+        assert_eq!(Pin::new(&mut c).poll_next(&mut cx), Poll::Ready(None));
+    }
+
+    #[test]
+    fn wake_producer() {
+        let mut q: AsyncSpscQueue<[usize; 1]> = AsyncSpscQueue::new();
+        let (mut p, mut c) = q.split();
+        let w = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(w.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(p.try_push(1).is_ok());
+        assert_eq!(Pin::new(&mut p).poll_ready(&mut cx), Poll::Pending);
+        assert_eq!(c.try_pop(), Some(1));
+        assert_eq!(w.0.load(Ordering::SeqCst), 1);
+        assert_eq!(Pin::new(&mut p).poll_ready(&mut cx), Poll::Ready(Ok(())));
+    }
+}
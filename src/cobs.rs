@@ -0,0 +1,283 @@
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+
+use arrayvec::{Array, ArrayVec};
+
+use super::array_queue::ArrayQueue;
+use super::error::{CapacityError, CobsError};
+
+const MAX_BLOCK_LENGTH: usize = 254;
+
+/// Encodes a packet with COBS and appends it to a queue followed by a zero
+/// delimiter.
+///
+/// Nothing is written if the queue does not have enough space for the whole
+/// frame.
+pub fn cobs_encode<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>>(
+    packet: &[u8],
+    queue: &mut ArrayQueue<A>,
+) -> Result<(), CapacityError> {
+    let mut n = 0;
+
+    encode(packet, |_| n += 1);
+
+    if n > queue.remaining_capacity() {
+        return Err(CapacityError::new(()));
+    }
+
+    encode(packet, |b| queue.push_back(b).ok().unwrap());
+
+    Ok(())
+}
+
+fn encode<F: FnMut(u8)>(mut xs: &[u8], mut f: F) {
+    loop {
+        let zero = xs.iter().take(MAX_BLOCK_LENGTH).position(|&b| b == 0);
+        let n = zero.unwrap_or_else(|| xs.len().min(MAX_BLOCK_LENGTH));
+
+        f(n as u8 + 1);
+
+        for &b in &xs[..n] {
+            f(b);
+        }
+
+        if zero.is_some() {
+            xs = &xs[n + 1..];
+        } else if n < xs.len() {
+            xs = &xs[n..];
+        } else {
+            break;
+        }
+    }
+
+    f(0);
+}
+
+/// A decoder of COBS frames delimited by zeros.
+///
+/// Bytes can be fed to it in chunks of any sizes. Decoded packets are
+/// returned as byte vectors which dereference to slices.
+pub struct CobsDecoder<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> {
+    packet: ArrayVec<A>,
+    // A code of the current block and the number of its bytes left. The code
+    // is zero before the first block of a frame.
+    code: u8,
+    remaining: u8,
+    error: Option<CobsError>,
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> CobsDecoder<A> {
+    pub fn new() -> Self {
+        CobsDecoder {
+            packet: ArrayVec::new(),
+            code: 0,
+            remaining: 0,
+            error: None,
+        }
+    }
+
+    /// Feeds a byte and returns a packet if it completes a frame.
+    pub fn push(&mut self, b: u8) -> Option<Result<ArrayVec<A>, CobsError>> {
+        if b == 0 {
+            return self.finish();
+        }
+
+        if self.error.is_some() {
+            return None;
+        }
+
+        if self.remaining == 0 {
+            if self.code != 0 && self.code as usize <= MAX_BLOCK_LENGTH {
+                self.write(0);
+            }
+
+            self.code = b;
+            self.remaining = b - 1;
+        } else {
+            self.write(b);
+            self.remaining -= 1;
+        }
+
+        None
+    }
+
+    /// Consumes bytes in a queue until a frame is completed.
+    pub fn decode<B: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>>(
+        &mut self,
+        queue: &mut ArrayQueue<B>,
+    ) -> Option<Result<ArrayVec<A>, CobsError>> {
+        while let Some(b) = queue.pop_front() {
+            if let Some(r) = self.push(b) {
+                return Some(r);
+            }
+        }
+
+        None
+    }
+
+    /// Discards a partially received frame.
+    pub fn reset(&mut self) {
+        self.packet.clear();
+        self.code = 0;
+        self.remaining = 0;
+        self.error = None;
+    }
+
+    fn write(&mut self, b: u8) {
+        if self.packet.try_push(b).is_err() {
+            self.error = Some(CobsError::Capacity);
+        }
+    }
+
+    fn finish(&mut self) -> Option<Result<ArrayVec<A>, CobsError>> {
+        let r = if let Some(e) = self.error {
+            Err(e)
+        } else if self.code == 0 {
+            // Consecutive delimiters do not make empty frames.
+            return None;
+        } else if self.remaining != 0 {
+            Err(CobsError::Malformed)
+        } else {
+            Ok(mem::take(&mut self.packet))
+        };
+
+        self.reset();
+        Some(r)
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Debug for CobsDecoder<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CobsDecoder")
+            .field("packet", &self.packet)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<A: Array<Item = u8> + AsRef<[u8]> + AsMut<[u8]>> Default for CobsDecoder<A> {
+    fn default() -> Self {
+        CobsDecoder::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        for (xs, ys) in &[
+            (&[][..], &[1, 0][..]),
+            (&[0], &[1, 1, 0]),
+            (&[0, 0], &[1, 1, 1, 0]),
+            (&[1, 2, 0, 3], &[3, 1, 2, 2, 3, 0]),
+            (&[1, 2, 3, 0], &[4, 1, 2, 3, 1, 0]),
+        ] {
+            let mut q: ArrayQueue<[u8; 8]> = ArrayQueue::new();
+
+            assert!(cobs_encode(xs, &mut q).is_ok());
+            assert_eq!(q.as_contiguous_slice(), *ys);
+        }
+    }
+
+    #[test]
+    fn encode_long_blocks() {
+        let xs = (1..=254).collect::<Vec<u8>>();
+        let mut ys = vec![0xff];
+        ys.extend(&xs);
+        ys.push(0);
+
+        let mut q: ArrayQueue<[u8; 512]> = ArrayQueue::new();
+
+        assert!(cobs_encode(&xs, &mut q).is_ok());
+        assert_eq!(q.as_contiguous_slice(), &ys[..]);
+
+        let mut xs = xs;
+        xs.push(0);
+        let mut ys = vec![0xff];
+        ys.extend(&xs[..254]);
+        ys.extend(&[1, 1, 0]);
+
+        q.clear();
+
+        assert!(cobs_encode(&xs, &mut q).is_ok());
+        assert_eq!(q.as_contiguous_slice(), &ys[..]);
+    }
+
+    #[test]
+    fn encode_into_small_queue() {
+        let mut q: ArrayQueue<[u8; 4]> = ArrayQueue::new();
+
+        assert!(cobs_encode(&[1, 2], &mut q).is_ok());
+        assert_eq!(cobs_encode(&[], &mut q), Err(CapacityError::new(())));
+        assert_eq!(q.len(), 4);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut x: u64 = 42;
+        let mut q: ArrayQueue<[u8; 1024]> = ArrayQueue::new();
+        let mut d: CobsDecoder<[u8; 512]> = CobsDecoder::new();
+
+        for n in &[0, 1, 2, 253, 254, 255, 256, 500] {
+            let xs = (0..*n)
+                .map(|_| {
+                    x = (x * 1103515245 + 12345) % 2147483648;
+                    match x % 3 {
+                        0 => 0,
+                        _ => (x >> 8) as u8,
+                    }
+                })
+                .collect::<Vec<u8>>();
+            let ys = (1..=*n).map(|i| (i % 255 + 1) as u8).collect::<Vec<u8>>();
+
+            for zs in &[xs, ys] {
+                assert!(cobs_encode(zs, &mut q).is_ok());
+                assert_eq!(&*d.decode(&mut q).unwrap().unwrap(), &zs[..]);
+                assert!(q.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn decode_partial_input() {
+        let mut input: ArrayQueue<[u8; 16]> = ArrayQueue::new();
+        let mut d: CobsDecoder<[u8; 8]> = CobsDecoder::new();
+
+        assert!(cobs_encode(&[1, 0, 2], &mut input).is_ok());
+        assert!(input.pop_back().is_some());
+        assert_eq!(d.decode(&mut input), None);
+        assert!(input.is_empty());
+
+        assert!(input.push_back(0).is_ok());
+        assert!(cobs_encode(&[3], &mut input).is_ok());
+
+        assert_eq!(&*d.decode(&mut input).unwrap().unwrap(), &[1, 0, 2]);
+        assert_eq!(input.len(), 3);
+        assert_eq!(&*d.decode(&mut input).unwrap().unwrap(), &[3]);
+    }
+
+    #[test]
+    fn decode_errors() {
+        let mut d: CobsDecoder<[u8; 4]> = CobsDecoder::new();
+
+        let rs = [0, 0, 3, 1, 0, 2, 1, 0]
+            .iter()
+            .filter_map(|&b| d.push(b))
+            .map(|r| r.map(|p| p.to_vec()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(rs, vec![Err(CobsError::Malformed), Ok(vec![1])]);
+
+        let mut d: CobsDecoder<[u8; 2]> = CobsDecoder::new();
+
+        let rs = [4, 1, 2, 3, 0, 2, 1, 0]
+            .iter()
+            .filter_map(|&b| d.push(b))
+            .map(|r| r.map(|p| p.to_vec()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(rs, vec![Err(CobsError::Capacity), Ok(vec![1])]);
+    }
+}
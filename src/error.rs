@@ -1,19 +1,87 @@
 use std::error::Error;
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{Debug, Display, Formatter, Result};
 
-const MESSAGE: &'static str = "queue is full";
+const MESSAGE: &str = "insufficient capacity";
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct CapacityError;
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    pub fn new(element: T) -> Self {
+        CapacityError { element }
+    }
 
-impl Display for CapacityError {
+    pub fn element(self) -> T {
+        self.element
+    }
+}
+
+impl<T> Display for CapacityError<T> {
     fn fmt(&self, f: &mut Formatter) -> Result {
         write!(f, "{}", MESSAGE)
     }
 }
 
-impl Error for CapacityError {
+impl<T: Debug> Error for CapacityError<T> {
     fn description(&self) -> &str {
         MESSAGE
     }
 }
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CStringError {
+    Capacity,
+    InteriorNul,
+    NotNulTerminated,
+}
+
+impl CStringError {
+    fn message(&self) -> &'static str {
+        match self {
+            CStringError::Capacity => MESSAGE,
+            CStringError::InteriorNul => "interior nul byte",
+            CStringError::NotNulTerminated => "missing nul terminator",
+        }
+    }
+}
+
+impl Display for CStringError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl Error for CStringError {
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CobsError {
+    Capacity,
+    Malformed,
+}
+
+impl CobsError {
+    fn message(&self) -> &'static str {
+        match self {
+            CobsError::Capacity => MESSAGE,
+            CobsError::Malformed => "malformed frame",
+        }
+    }
+}
+
+impl Display for CobsError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl Error for CobsError {
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
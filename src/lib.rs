@@ -1,6 +1,86 @@
 extern crate arrayvec;
+#[cfg(feature = "futures")]
+extern crate futures_core;
+#[cfg(feature = "futures")]
+extern crate futures_sink;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 
+mod array_2d;
+mod array_arena;
+mod array_binary_heap;
+mod array_bit_set;
+mod array_c_string;
+mod array_gap_buffer;
+mod array_index_map;
+mod array_lru;
+mod array_map;
+mod array_min_max_heap;
+mod array_mpmc_queue;
+mod array_path_buf;
+mod array_pool;
 mod array_queue;
+mod array_set;
+mod array_shape;
+mod array_sorted_vec;
+mod array_spsc_queue;
+mod array_stack;
+#[macro_use]
+mod array_string;
+#[cfg(feature = "futures")]
+mod async_spsc_queue;
+mod cobs;
 mod error;
+mod log_ring;
+mod monotonic_queue;
+mod moving_median;
+mod recent_set;
+mod shared_spsc_queue;
+mod sliding_window;
+mod sorted_array_map;
+#[cfg(feature = "std")]
+mod sync_array_queue;
+mod timer_wheel;
+mod triple_buffer;
+mod undo_history;
+mod window_stats;
 
+pub use array_2d::Array2D;
+pub use array_arena::{ArrayArena, ArrayArenaKey};
+pub use array_binary_heap::ArrayBinaryHeap;
+pub use array_bit_set::ArrayBitSet;
+pub use array_c_string::ArrayCString;
+pub use array_gap_buffer::ArrayGapBuffer;
+pub use array_index_map::ArrayIndexMap;
+pub use array_lru::ArrayLru;
+pub use array_map::{ArrayMap, Entry, OccupiedEntry, VacantEntry};
+pub use array_min_max_heap::ArrayMinMaxHeap;
+pub use array_mpmc_queue::ArrayMpmcQueue;
+pub use array_path_buf::ArrayPathBuf;
+pub use array_pool::{ArrayPool, ArrayPoolGuard};
 pub use array_queue::ArrayQueue;
+pub use array_set::ArraySet;
+pub use array_shape::ArrayShape;
+pub use array_sorted_vec::ArraySortedVec;
+pub use array_spsc_queue::{ArraySpscQueue, Consumer, Producer};
+pub use array_stack::ArrayStack;
+pub use array_string::ArrayString;
+#[cfg(feature = "futures")]
+pub use async_spsc_queue::{AsyncConsumer, AsyncProducer, AsyncSpscQueue};
+pub use cobs::{cobs_encode, CobsDecoder};
+pub use error::{CStringError, CapacityError, CobsError};
+pub use log_ring::LogRing;
+pub use monotonic_queue::MonotonicQueue;
+pub use moving_median::MovingMedian;
+pub use recent_set::RecentSet;
+pub use shared_spsc_queue::SharedSpscQueue;
+pub use sliding_window::SlidingWindow;
+pub use sorted_array_map::SortedArrayMap;
+#[cfg(feature = "std")]
+pub use sync_array_queue::SyncArrayQueue;
+pub use timer_wheel::TimerWheel;
+pub use triple_buffer::{TripleBuffer, TripleBufferReader, TripleBufferWriter};
+pub use undo_history::UndoHistory;
+pub use window_stats::WindowStats;
@@ -0,0 +1,213 @@
+use std::fmt::{self, Debug, Formatter};
+use std::iter::FusedIterator;
+
+use arrayvec::Array;
+
+use super::array_queue::{ArrayQueue, ArrayQueueIterator};
+
+/// A ring buffer of records numbered by sequence numbers.
+///
+/// Sequence numbers start from zero and increase by one on every push. When
+/// the ring is full, the oldest record is overwritten.
+pub struct LogRing<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    records: ArrayQueue<A>,
+    next_sequence: u64,
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> LogRing<A> {
+    pub fn new() -> Self {
+        LogRing {
+            records: ArrayQueue::new(),
+            next_sequence: 0,
+        }
+    }
+
+    /// Pushes a record and returns its sequence number.
+    pub fn push(&mut self, x: <A as Array>::Item) -> u64 {
+        self.records.push_back_overwrite(x);
+        self.next_sequence += 1;
+        self.next_sequence - 1
+    }
+
+    /// Returns records whose sequence numbers are equal to or greater than a
+    /// given one. Pass `next_sequence` saved on a previous read as a cursor
+    /// to get only new records.
+    pub fn read_since(&self, sequence: u64) -> LogRingIterator<'_, A> {
+        let first = self.first_sequence();
+        let mut records = self.records.into_iter();
+
+        for _ in first..sequence.max(first).min(self.next_sequence) {
+            records.next();
+        }
+
+        LogRingIterator {
+            records,
+            sequence: sequence.max(first),
+        }
+    }
+
+    pub fn iter(&self) -> LogRingIterator<'_, A> {
+        self.read_since(0)
+    }
+
+    /// Returns a sequence number of the oldest record. Records before it have
+    /// been overwritten.
+    pub fn first_sequence(&self) -> u64 {
+        self.next_sequence - self.records.len() as u64
+    }
+
+    /// Returns a sequence number which will be given to the next record.
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Removes all records. Sequence numbers are not reset.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.records.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.records.capacity()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Clone for LogRing<A>
+where
+    <A as Array>::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        LogRing {
+            records: self.records.clone(),
+            next_sequence: self.next_sequence,
+        }
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Debug for LogRing<A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Default for LogRing<A> {
+    fn default() -> Self {
+        LogRing::new()
+    }
+}
+
+pub struct LogRingIterator<
+    'a,
+    A: 'a + Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>,
+> {
+    records: ArrayQueueIterator<'a, A>,
+    sequence: u64,
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Iterator
+    for LogRingIterator<'a, A>
+{
+    type Item = (u64, &'a <A as Array>::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.records.next()?;
+        self.sequence += 1;
+        Some((self.sequence - 1, x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.records.size_hint()
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> ExactSizeIterator
+    for LogRingIterator<'a, A>
+{
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> FusedIterator
+    for LogRingIterator<'a, A>
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        LogRing::<[usize; 0]>::new();
+        LogRing::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push() {
+        let mut r: LogRing<[&str; 2]> = LogRing::new();
+
+        assert_eq!(r.push("a"), 0);
+        assert_eq!(r.push("b"), 1);
+        assert_eq!(r.push("c"), 2);
+        assert_eq!(r.first_sequence(), 1);
+        assert_eq!(r.next_sequence(), 3);
+        assert_eq!(r.iter().collect::<Vec<_>>(), vec![(1, &"b"), (2, &"c")]);
+    }
+
+    #[test]
+    fn push_into_zero_capacity() {
+        let mut r: LogRing<[usize; 0]> = LogRing::new();
+
+        assert_eq!(r.push(1), 0);
+        assert_eq!(r.push(2), 1);
+        assert_eq!(r.first_sequence(), 2);
+        assert_eq!(r.iter().next(), None);
+    }
+
+    #[test]
+    fn read_since() {
+        let mut r: LogRing<[usize; 4]> = LogRing::new();
+
+        for x in 0..6 {
+            r.push(10 * x);
+        }
+
+        assert_eq!(r.read_since(0).collect::<Vec<_>>()[0], (2, &20));
+        assert_eq!(
+            r.read_since(4).collect::<Vec<_>>(),
+            vec![(4, &40), (5, &50)]
+        );
+        assert_eq!(r.read_since(4).len(), 2);
+        assert_eq!(r.read_since(6).next(), None);
+        assert_eq!(r.read_since(100).next(), None);
+
+        let cursor = r.next_sequence();
+        r.push(60);
+
+        assert_eq!(r.read_since(cursor).collect::<Vec<_>>(), vec![(6, &60)]);
+    }
+
+    #[test]
+    fn clear() {
+        let mut r: LogRing<[usize; 4]> = LogRing::new();
+
+        r.push(1);
+        r.clear();
+
+        assert!(r.is_empty());
+        assert_eq!(r.push(2), 1);
+        assert_eq!(r.first_sequence(), 1);
+    }
+}
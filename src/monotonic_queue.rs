@@ -0,0 +1,163 @@
+use std::fmt::{self, Debug, Formatter};
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+/// A queue which keeps only elements that can still become its maximum.
+///
+/// Push values entering a sliding window and evict values leaving it to get
+/// the maximum of the window in amortized O(1) time. Wrap elements in
+/// `std::cmp::Reverse` for the minimum.
+pub struct MonotonicQueue<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    elements: ArrayQueue<A>,
+}
+
+impl<T: PartialOrd, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> MonotonicQueue<A> {
+    pub fn new() -> Self {
+        MonotonicQueue {
+            elements: ArrayQueue::new(),
+        }
+    }
+
+    pub fn push(&mut self, x: T) -> Result<(), CapacityError<T>> {
+        while self.elements.last().map(|y| *y < x).unwrap_or(false) {
+            self.elements.pop_back();
+        }
+
+        self.elements.push_back(x)
+    }
+
+    /// Removes the maximum if it is equal to a given value which has left the
+    /// window.
+    pub fn evict(&mut self, x: &T) -> bool {
+        if self.max() == Some(x) {
+            self.elements.pop_front();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.elements.pop_front()
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.elements.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+}
+
+impl<T: Clone, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Clone for MonotonicQueue<A> {
+    fn clone(&self) -> Self {
+        MonotonicQueue {
+            elements: self.elements.clone(),
+        }
+    }
+}
+
+impl<T: Debug, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Debug for MonotonicQueue<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.elements, f)
+    }
+}
+
+impl<T: PartialOrd, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Default for MonotonicQueue<A> {
+    fn default() -> Self {
+        MonotonicQueue::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::cmp::Reverse;
+
+    #[test]
+    fn new() {
+        MonotonicQueue::<[usize; 0]>::new();
+        MonotonicQueue::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push() {
+        let mut q: MonotonicQueue<[usize; 4]> = MonotonicQueue::new();
+
+        assert_eq!(q.max(), None);
+        assert!(q.push(1).is_ok());
+        assert!(q.push(3).is_ok());
+        assert_eq!(q.max(), Some(&3));
+        assert!(q.push(2).is_ok());
+        assert_eq!(q.len(), 2);
+        assert_eq!(q.pop_max(), Some(3));
+        assert_eq!(q.max(), Some(&2));
+    }
+
+    #[test]
+    fn push_into_full_queue() {
+        let mut q: MonotonicQueue<[usize; 2]> = MonotonicQueue::new();
+
+        assert!(q.push(3).is_ok());
+        assert!(q.push(2).is_ok());
+        assert_eq!(q.push(1), Err(CapacityError::new(1)));
+        assert!(q.push(4).is_ok());
+        assert_eq!(q.max(), Some(&4));
+    }
+
+    #[test]
+    fn evict() {
+        let mut q: MonotonicQueue<[usize; 4]> = MonotonicQueue::new();
+
+        assert!(q.push(2).is_ok());
+        assert!(q.push(2).is_ok());
+        assert!(q.push(1).is_ok());
+        assert!(!q.evict(&1));
+        assert!(q.evict(&2));
+        assert_eq!(q.max(), Some(&2));
+        assert!(q.evict(&2));
+        assert_eq!(q.max(), Some(&1));
+    }
+
+    #[test]
+    fn sliding_window_minimum() {
+        let xs = [4, 2, 12, 3, 8, 5, 7, 1];
+        let mut q: MonotonicQueue<[Reverse<usize>; 3]> = MonotonicQueue::new();
+        let mut ys = vec![];
+
+        for (i, &x) in xs.iter().enumerate() {
+            if i >= 3 {
+                q.evict(&Reverse(xs[i - 3]));
+            }
+
+            assert!(q.push(Reverse(x)).is_ok());
+
+            if i >= 2 {
+                ys.push(q.max().unwrap().0);
+            }
+        }
+
+        assert_eq!(ys, vec![2, 2, 3, 3, 5, 1]);
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::{self, Debug, Formatter};
+
+use arrayvec::Array;
+
+use super::array_sorted_vec::ArraySortedVec;
+use super::sliding_window::SlidingWindow;
+
+/// A median of the last N values.
+///
+/// The values are kept both in arrival order and in sorted order, so each
+/// push takes O(N) time.
+pub struct MovingMedian<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    window: SlidingWindow<A>,
+    sorted: ArraySortedVec<A>,
+}
+
+impl<T: Clone + Ord, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> MovingMedian<A> {
+    pub fn new() -> Self {
+        MovingMedian {
+            window: SlidingWindow::new(),
+            sorted: ArraySortedVec::new(),
+        }
+    }
+
+    pub fn push(&mut self, x: T) -> Option<T> {
+        if self.capacity() == 0 {
+            return Some(x);
+        }
+
+        let y = self.window.push(x.clone());
+
+        if let Some(ref y) = y {
+            self.sorted.remove(y);
+        }
+
+        self.sorted.insert(x).ok().unwrap();
+
+        y
+    }
+
+    /// Returns the median. The lower one of the two middle values is returned
+    /// when the number of values is even.
+    pub fn median(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.sorted.as_slice().get((self.len() - 1) / 2)
+    }
+
+    pub fn window(&self) -> &SlidingWindow<A> {
+        &self.window
+    }
+
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.sorted.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+impl<T: Clone, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Clone for MovingMedian<A> {
+    fn clone(&self) -> Self {
+        MovingMedian {
+            window: self.window.clone(),
+            sorted: self.sorted.clone(),
+        }
+    }
+}
+
+impl<T: Debug, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Debug for MovingMedian<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("MovingMedian").field(&self.window).finish()
+    }
+}
+
+impl<T: Clone + Ord, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Default for MovingMedian<A> {
+    fn default() -> Self {
+        MovingMedian::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        MovingMedian::<[usize; 0]>::new();
+        MovingMedian::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push() {
+        let mut m: MovingMedian<[usize; 3]> = MovingMedian::new();
+
+        assert_eq!(m.median(), None);
+        assert_eq!(m.push(5), None);
+        assert_eq!(m.median(), Some(&5));
+        assert_eq!(m.push(1), None);
+        assert_eq!(m.median(), Some(&1));
+        assert_eq!(m.push(3), None);
+        assert_eq!(m.median(), Some(&3));
+        assert_eq!(m.push(9), Some(5));
+        assert_eq!(m.median(), Some(&3));
+        assert_eq!(m.push(7), Some(1));
+        assert_eq!(m.median(), Some(&7));
+    }
+
+    #[test]
+    fn push_into_zero_capacity() {
+        let mut m: MovingMedian<[usize; 0]> = MovingMedian::new();
+
+        assert_eq!(m.push(1), Some(1));
+        assert_eq!(m.median(), None);
+    }
+
+    #[test]
+    fn compare_with_naive_implementation() {
+        let mut m: MovingMedian<[u64; 7]> = MovingMedian::new();
+        let mut xs = vec![];
+        let mut x: u64 = 42;
+
+        for _ in 0..1000 {
+            x = (x * 1103515245 + 12345) % 2147483648;
+            let y = x % 20;
+
+            m.push(y);
+            xs.push(y);
+
+            let mut w = xs[xs.len().saturating_sub(7)..].to_vec();
+            w.sort();
+
+            assert_eq!(m.median(), Some(&w[(w.len() - 1) / 2]));
+        }
+    }
+}
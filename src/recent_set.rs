@@ -0,0 +1,112 @@
+use std::fmt::{self, Debug, Formatter};
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueueIterator;
+use super::sliding_window::SlidingWindow;
+
+/// A set of the last N inserted values which evicts the oldest ones first.
+pub struct RecentSet<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    window: SlidingWindow<A>,
+}
+
+impl<T: Clone + Eq, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> RecentSet<A> {
+    pub fn new() -> Self {
+        RecentSet {
+            window: SlidingWindow::new(),
+        }
+    }
+
+    /// Inserts a value if it has not been seen recently. Returns `true` if it
+    /// is inserted.
+    pub fn insert_if_new(&mut self, x: &T) -> bool {
+        if self.contains(x) {
+            return false;
+        }
+
+        self.window.push(x.clone());
+        true
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.iter().any(|y| y == x)
+    }
+
+    pub fn iter(&self) -> ArrayQueueIterator<'_, A> {
+        self.window.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.window.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+impl<T: Clone, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Clone for RecentSet<A> {
+    fn clone(&self) -> Self {
+        RecentSet {
+            window: self.window.clone(),
+        }
+    }
+}
+
+impl<T: Debug, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Debug for RecentSet<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_set().entries(self.window.iter()).finish()
+    }
+}
+
+impl<T: Clone + Eq, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Default for RecentSet<A> {
+    fn default() -> Self {
+        RecentSet::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        RecentSet::<[usize; 0]>::new();
+        RecentSet::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn insert_if_new() {
+        let mut s: RecentSet<[usize; 2]> = RecentSet::new();
+
+        assert!(s.insert_if_new(&1));
+        assert!(!s.insert_if_new(&1));
+        assert!(s.insert_if_new(&2));
+        assert!(s.insert_if_new(&3));
+        assert!(!s.contains(&1));
+        assert!(s.insert_if_new(&1));
+        assert!(!s.insert_if_new(&3));
+        assert_eq!(s.iter().cloned().collect::<Vec<_>>(), vec![3, 1]);
+    }
+
+    #[test]
+    fn insert_into_zero_capacity() {
+        let mut s: RecentSet<[usize; 0]> = RecentSet::new();
+
+        assert!(s.insert_if_new(&1));
+        assert!(s.insert_if_new(&1));
+        assert!(s.is_empty());
+    }
+}
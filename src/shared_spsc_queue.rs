@@ -0,0 +1,264 @@
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug, Formatter};
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use arrayvec::Array;
+
+use super::error::CapacityError;
+
+/// A single-producer single-consumer queue with a stable memory layout.
+///
+/// The queue can be placed in a static or in memory shared between processes
+/// and accessed from C code. The layout of a queue of `[T; N]` is equivalent
+/// to the following C struct.
+///
+/// ```c
+/// struct shared_spsc_queue {
+///     _Atomic uint32_t head;
+///     _Atomic uint32_t tail;
+///     T buffer[N];
+/// };
+/// ```
+///
+/// Both indices run over `0..2 * N`. An element at an index `i` is stored in
+/// `buffer[i % N]`. The queue is empty when `head == tail` and full when the
+/// indices differ by `N` modulo `2 * N`. The consumer reads an element before
+/// it advances `head` and the producer writes an element before it advances
+/// `tail`, both with release ordering. All-zero memory is an empty queue.
+#[repr(C)]
+pub struct SharedSpscQueue<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    head: AtomicU32,
+    tail: AtomicU32,
+    buffer: UnsafeCell<MaybeUninit<A>>,
+}
+
+unsafe impl<T: Copy + Send, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Sync
+    for SharedSpscQueue<A>
+{
+}
+
+impl<T: Copy, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> SharedSpscQueue<A> {
+    pub const HEAD_OFFSET: usize = mem::offset_of!(Self, head);
+    pub const TAIL_OFFSET: usize = mem::offset_of!(Self, tail);
+    pub const BUFFER_OFFSET: usize = mem::offset_of!(Self, buffer);
+    pub const SIZE: usize = mem::size_of::<Self>();
+    pub const ALIGN: usize = mem::align_of::<Self>();
+
+    pub const fn new() -> Self {
+        SharedSpscQueue {
+            head: AtomicU32::new(0),
+            tail: AtomicU32::new(0),
+            buffer: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Gets a queue from raw memory.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be aligned to `ALIGN` and valid for reads and writes
+    /// of `SIZE` bytes for the lifetime `'a`. The memory must be zeroed or
+    /// hold a queue of the same element type and capacity.
+    pub unsafe fn from_raw<'a>(p: *mut u8) -> &'a Self {
+        &*(p as *const Self)
+    }
+
+    /// Pushes an element to the back of the queue.
+    ///
+    /// # Safety
+    ///
+    /// Only one thread or process can push elements to the queue at a time.
+    pub unsafe fn push(&self, x: T) -> Result<(), CapacityError<T>> {
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        let n = self.capacity();
+
+        if n == 0 || distance(self.head.load(Ordering::Acquire), tail, n) == n as u32 {
+            return Err(CapacityError::new(x));
+        }
+
+        ptr::write(self.slot(tail), x);
+        self.tail.store(increment(tail, n), Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Pops an element from the front of the queue.
+    ///
+    /// # Safety
+    ///
+    /// Only one thread or process can pop elements from the queue at a time.
+    pub unsafe fn pop(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let x = ptr::read(self.slot(head));
+        self.head
+            .store(increment(head, self.capacity()), Ordering::Release);
+
+        Some(x)
+    }
+
+    pub fn len(&self) -> usize {
+        distance(
+            self.head.load(Ordering::Acquire),
+            self.tail.load(Ordering::Acquire),
+            self.capacity(),
+        ) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    fn slot(&self, i: u32) -> *mut T {
+        unsafe { (self.buffer.get() as *mut T).add(i as usize % self.capacity()) }
+    }
+}
+
+fn distance(head: u32, tail: u32, n: usize) -> u32 {
+    if tail >= head {
+        tail - head
+    } else {
+        tail + 2 * n as u32 - head
+    }
+}
+
+fn increment(i: u32, n: usize) -> u32 {
+    (i + 1) % (2 * n as u32)
+}
+
+impl<T: Copy, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Debug for SharedSpscQueue<A> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SharedSpscQueue")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T: Copy, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Default for SharedSpscQueue<A> {
+    fn default() -> Self {
+        SharedSpscQueue::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::thread;
+
+    #[test]
+    fn new() {
+        SharedSpscQueue::<[u32; 0]>::new();
+        SharedSpscQueue::<[u32; 8]>::new();
+    }
+
+    #[test]
+    fn layout() {
+        type Queue = SharedSpscQueue<[u64; 4]>;
+
+        assert_eq!(Queue::HEAD_OFFSET, 0);
+        assert_eq!(Queue::TAIL_OFFSET, 4);
+        assert_eq!(Queue::BUFFER_OFFSET, 8);
+        assert_eq!(Queue::SIZE, 40);
+        assert_eq!(Queue::ALIGN, 8);
+        assert_eq!(SharedSpscQueue::<[u8; 3]>::SIZE, 12);
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let q: SharedSpscQueue<[u32; 2]> = SharedSpscQueue::new();
+
+        unsafe {
+            assert_eq!(q.pop(), None);
+            assert!(q.push(1).is_ok());
+            assert!(q.push(2).is_ok());
+            assert_eq!(q.push(3), Err(CapacityError::new(3)));
+            assert!(q.is_full());
+            assert_eq!(q.pop(), Some(1));
+            assert!(q.push(3).is_ok());
+            assert_eq!(q.pop(), Some(2));
+            assert_eq!(q.pop(), Some(3));
+            assert_eq!(q.pop(), None);
+        }
+
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let q: SharedSpscQueue<[u32; 0]> = SharedSpscQueue::new();
+
+        unsafe {
+            assert_eq!(q.push(1), Err(CapacityError::new(1)));
+            assert_eq!(q.pop(), None);
+        }
+    }
+
+    #[test]
+    fn from_raw() {
+        type Queue = SharedSpscQueue<[u32; 4]>;
+
+        let mut memory = [0u64; Queue::SIZE / 8];
+        let p = memory.as_mut_ptr() as *mut u8;
+
+        unsafe {
+            let q = Queue::from_raw(p);
+
+            assert!(q.is_empty());
+            assert!(q.push(42).is_ok());
+            assert!(q.push(43).is_ok());
+            assert!(q.pop().is_some());
+
+            assert_eq!(*(p.add(Queue::HEAD_OFFSET) as *const u32), 1);
+            assert_eq!(*(p.add(Queue::TAIL_OFFSET) as *const u32), 2);
+            assert_eq!(*(p.add(Queue::BUFFER_OFFSET) as *const u32).add(1), 43);
+
+            assert_eq!(Queue::from_raw(p).pop(), Some(43));
+        }
+    }
+
+    #[test]
+    fn static_queue() {
+        static QUEUE: SharedSpscQueue<[usize; 8]> = SharedSpscQueue::new();
+        const N: usize = 10000;
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                for i in 0..N {
+                    while unsafe { QUEUE.push(i) }.is_err() {
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            s.spawn(|| {
+                let mut i = 0;
+
+                while i < N {
+                    if let Some(x) = unsafe { QUEUE.pop() } {
+                        assert_eq!(x, i);
+                        i += 1;
+                    } else {
+                        thread::yield_now();
+                    }
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt::{self, Debug, Formatter};
+
+use arrayvec::Array;
+
+use super::array_queue::{ArrayQueue, ArrayQueueIterator};
+
+/// A buffer of the last N elements. Pushing into a full window evicts its
+/// oldest element.
+pub struct SlidingWindow<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    elements: ArrayQueue<A>,
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> SlidingWindow<A> {
+    pub fn new() -> Self {
+        SlidingWindow {
+            elements: ArrayQueue::new(),
+        }
+    }
+
+    pub fn push(&mut self, x: <A as Array>::Item) -> Option<<A as Array>::Item> {
+        self.elements.push_back_overwrite(x)
+    }
+
+    pub fn oldest(&self) -> Option<&<A as Array>::Item> {
+        self.elements.first()
+    }
+
+    pub fn newest(&self) -> Option<&<A as Array>::Item> {
+        self.elements.last()
+    }
+
+    pub fn iter(&self) -> ArrayQueueIterator<'_, A> {
+        self.elements.into_iter()
+    }
+
+    pub fn as_slices(&self) -> (&[<A as Array>::Item], &[<A as Array>::Item]) {
+        self.elements.as_slices()
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.elements.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Clone
+    for SlidingWindow<A>
+where
+    <A as Array>::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        SlidingWindow {
+            elements: self.elements.clone(),
+        }
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Debug
+    for SlidingWindow<A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(&self.elements, f)
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Default
+    for SlidingWindow<A>
+{
+    fn default() -> Self {
+        SlidingWindow::new()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>>
+    Extend<<A as Array>::Item> for SlidingWindow<A>
+{
+    fn extend<I: IntoIterator<Item = <A as Array>::Item>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<'a, A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> IntoIterator
+    for &'a SlidingWindow<A>
+{
+    type Item = &'a <A as Array>::Item;
+    type IntoIter = ArrayQueueIterator<'a, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        SlidingWindow::<[usize; 0]>::new();
+        SlidingWindow::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn push() {
+        let mut w: SlidingWindow<[usize; 3]> = SlidingWindow::new();
+
+        assert_eq!(w.push(1), None);
+        assert_eq!(w.push(2), None);
+        assert_eq!(w.push(3), None);
+        assert!(w.is_full());
+        assert_eq!(w.push(4), Some(1));
+        assert_eq!(w.push(5), Some(2));
+        assert_eq!(w.oldest(), Some(&3));
+        assert_eq!(w.newest(), Some(&5));
+        assert_eq!(w.len(), 3);
+    }
+
+    #[test]
+    fn push_into_zero_capacity() {
+        let mut w: SlidingWindow<[usize; 0]> = SlidingWindow::new();
+
+        assert_eq!(w.push(1), Some(1));
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn iter() {
+        let mut w: SlidingWindow<[usize; 3]> = SlidingWindow::new();
+
+        w.extend(0..5);
+
+        assert_eq!(w.iter().cloned().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(
+            (&w).into_iter().rev().cloned().collect::<Vec<_>>(),
+            vec![4, 3, 2]
+        );
+    }
+}
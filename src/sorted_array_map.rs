@@ -0,0 +1,226 @@
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+
+use arrayvec::Array;
+
+use super::array_map::{ArrayMapIterator, ArrayMapMutIterator};
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+pub struct SortedArrayMap<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    entries: ArrayQueue<A>,
+}
+
+impl<K: Ord, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> SortedArrayMap<A> {
+    pub fn new() -> Self {
+        SortedArrayMap {
+            entries: ArrayQueue::new(),
+        }
+    }
+
+    pub fn insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+        match self.search(&k) {
+            Ok(i) => Ok(self
+                .entries
+                .element_mut(i)
+                .map(|e| mem::replace(&mut e.1, v))),
+            Err(i) => {
+                self.entries.insert(i, (k, v))?;
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn get<'a>(&'a self, k: &K) -> Option<&'a V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let i = self.search(k).ok()?;
+        self.entries.element(i).map(|(_, v)| v)
+    }
+
+    pub fn get_mut<'a>(&'a mut self, k: &K) -> Option<&'a mut V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let i = self.search(k).ok()?;
+        self.entries.element_mut(i).map(|(_, v)| v)
+    }
+
+    pub fn remove(&mut self, k: &K) -> Option<V> {
+        let i = self.search(k).ok()?;
+        self.entries.remove(i).map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.search(k).is_ok()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter<'a>(&'a self) -> ArrayMapIterator<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.into_iter()
+    }
+
+    pub fn iter_mut<'a>(&'a mut self) -> ArrayMapMutIterator<'a, K, V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    fn search(&self, k: &K) -> Result<usize, usize> {
+        self.entries.binary_search_by(|(l, _)| l.cmp(k))
+    }
+}
+
+impl<K: Clone, V: Clone, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Clone
+    for SortedArrayMap<A>
+{
+    fn clone(&self) -> Self {
+        SortedArrayMap {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Debug
+    for SortedArrayMap<A>
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<K: Ord, V, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> Default
+    for SortedArrayMap<A>
+{
+    fn default() -> Self {
+        SortedArrayMap::new()
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> IntoIterator
+    for &'a SortedArrayMap<A>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = ArrayMapIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayMapIterator::new(&self.entries)
+    }
+}
+
+impl<'a, K: 'a, V: 'a, A: Array<Item = (K, V)> + AsRef<[(K, V)]> + AsMut<[(K, V)]>> IntoIterator
+    for &'a mut SortedArrayMap<A>
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = ArrayMapMutIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayMapMutIterator::new(&mut self.entries)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        SortedArrayMap::<[(usize, usize); 0]>::new();
+        SortedArrayMap::<[(usize, usize); 4]>::new();
+    }
+
+    #[test]
+    fn insert() {
+        let mut m: SortedArrayMap<[(usize, &str); 3]> = SortedArrayMap::new();
+
+        assert_eq!(m.insert(2, "b"), Ok(None));
+        assert_eq!(m.insert(3, "c"), Ok(None));
+        assert_eq!(m.insert(1, "a"), Ok(None));
+        assert_eq!(m.insert(2, "d"), Ok(Some("b")));
+        assert_eq!(m.insert(0, "e"), Err(CapacityError::new((0, "e"))));
+        assert_eq!(format!("{:?}", m), "{1: \"a\", 2: \"d\", 3: \"c\"}");
+    }
+
+    #[test]
+    fn get() {
+        let mut m: SortedArrayMap<[(usize, usize); 8]> = SortedArrayMap::new();
+
+        for i in (0..8).rev() {
+            assert!(m.insert(i, i * 10).is_ok());
+        }
+
+        for i in 0..8 {
+            assert_eq!(m.get(&i), Some(&(i * 10)));
+        }
+
+        assert_eq!(m.get(&8), None);
+
+        *m.get_mut(&3).unwrap() += 1;
+
+        assert_eq!(m.get(&3), Some(&31));
+    }
+
+    #[test]
+    fn remove() {
+        let mut m: SortedArrayMap<[(usize, usize); 4]> = SortedArrayMap::new();
+
+        for i in 0..4 {
+            assert!(m.insert(i, i).is_ok());
+        }
+
+        assert_eq!(m.remove(&1), Some(1));
+        assert_eq!(m.remove(&1), None);
+        assert!(!m.contains_key(&1));
+        assert!(m.insert(1, 42).is_ok());
+        assert!(m.contains_key(&1));
+
+        let ks: Vec<usize> = m.iter().map(|(k, _)| *k).collect();
+
+        assert_eq!(ks, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn iterate_mutably() {
+        let mut m: SortedArrayMap<[(usize, usize); 4]> = SortedArrayMap::new();
+
+        for i in 0..4 {
+            assert!(m.insert(i, i).is_ok());
+        }
+
+        for (_, v) in &mut m {
+            *v *= 2;
+        }
+
+        for (k, v) in &m {
+            assert_eq!(*v, k * 2);
+        }
+    }
+}
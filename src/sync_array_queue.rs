@@ -0,0 +1,224 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::error::CapacityError;
+
+/// A queue shared between threads which can block until elements or space
+/// are available.
+pub struct SyncArrayQueue<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    queue: Mutex<ArrayQueue<A>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> SyncArrayQueue<A> {
+    pub fn new() -> Self {
+        SyncArrayQueue {
+            queue: Mutex::new(ArrayQueue::new()),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    pub fn try_push(&self, x: <A as Array>::Item) -> Result<(), CapacityError<<A as Array>::Item>> {
+        self.lock().push_back(x)?;
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    pub fn push_blocking(&self, x: <A as Array>::Item) {
+        let mut q = self.lock();
+
+        while q.is_full() {
+            q = self.not_full.wait(q).unwrap();
+        }
+
+        q.push_back(x).ok().unwrap();
+        self.not_empty.notify_one();
+    }
+
+    pub fn push_timeout(
+        &self,
+        x: <A as Array>::Item,
+        d: Duration,
+    ) -> Result<(), CapacityError<<A as Array>::Item>> {
+        let deadline = Instant::now() + d;
+        let mut q = self.lock();
+
+        while q.is_full() {
+            let now = Instant::now();
+
+            if now >= deadline {
+                return Err(CapacityError::new(x));
+            }
+
+            q = self.not_full.wait_timeout(q, deadline - now).unwrap().0;
+        }
+
+        q.push_back(x).ok().unwrap();
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    pub fn try_pop(&self) -> Option<<A as Array>::Item> {
+        let x = self.lock().pop_front()?;
+        self.not_full.notify_one();
+        Some(x)
+    }
+
+    pub fn pop_blocking(&self) -> <A as Array>::Item {
+        let mut q = self.lock();
+
+        loop {
+            if let Some(x) = q.pop_front() {
+                self.not_full.notify_one();
+                return x;
+            }
+
+            q = self.not_empty.wait(q).unwrap();
+        }
+    }
+
+    pub fn pop_timeout(&self, d: Duration) -> Option<<A as Array>::Item> {
+        let deadline = Instant::now() + d;
+        let mut q = self.lock();
+
+        loop {
+            if let Some(x) = q.pop_front() {
+                self.not_full.notify_one();
+                return Some(x);
+            }
+
+            let now = Instant::now();
+
+            if now >= deadline {
+                return None;
+            }
+
+            q = self.not_empty.wait_timeout(q, deadline - now).unwrap().0;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.lock().is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    pub fn into_inner(self) -> ArrayQueue<A> {
+        self.queue.into_inner().unwrap()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ArrayQueue<A>> {
+        self.queue.lock().unwrap()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Debug
+    for SyncArrayQueue<A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("SyncArrayQueue")
+            .field(&*self.lock())
+            .finish()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Default
+    for SyncArrayQueue<A>
+{
+    fn default() -> Self {
+        SyncArrayQueue::new()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> From<ArrayQueue<A>>
+    for SyncArrayQueue<A>
+{
+    fn from(q: ArrayQueue<A>) -> Self {
+        SyncArrayQueue {
+            queue: Mutex::new(q),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::thread;
+
+    #[test]
+    fn new() {
+        SyncArrayQueue::<[usize; 0]>::new();
+        SyncArrayQueue::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn try_push_and_pop() {
+        let q: SyncArrayQueue<[usize; 2]> = SyncArrayQueue::new();
+
+        assert!(q.try_push(1).is_ok());
+        assert!(q.try_push(2).is_ok());
+        assert_eq!(q.try_push(3), Err(CapacityError::new(3)));
+        assert!(q.is_full());
+        assert_eq!(q.try_pop(), Some(1));
+        assert_eq!(q.try_pop(), Some(2));
+        assert_eq!(q.try_pop(), None);
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn timeout() {
+        let q: SyncArrayQueue<[usize; 1]> = SyncArrayQueue::new();
+
+        assert_eq!(q.pop_timeout(Duration::from_millis(10)), None);
+        assert!(q.push_timeout(1, Duration::from_millis(10)).is_ok());
+        assert_eq!(
+            q.push_timeout(2, Duration::from_millis(10)),
+            Err(CapacityError::new(2))
+        );
+        assert_eq!(q.pop_timeout(Duration::from_millis(10)), Some(1));
+    }
+
+    #[test]
+    fn blocking() {
+        const N: usize = 1000;
+
+        let q: SyncArrayQueue<[usize; 4]> = SyncArrayQueue::new();
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                for i in 0..N {
+                    q.push_blocking(i);
+                }
+            });
+
+            s.spawn(|| {
+                for i in 0..N {
+                    assert_eq!(q.pop_blocking(), i);
+                }
+            });
+        });
+
+        assert!(q.into_inner().is_empty());
+    }
+}
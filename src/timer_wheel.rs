@@ -0,0 +1,501 @@
+use std::fmt::{self, Debug, Formatter};
+use std::iter::FusedIterator;
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::array_shape::ArrayShape;
+use super::error::CapacityError;
+
+/// A hierarchical timer wheel.
+///
+/// The wheel is parameterized by an array of levels each of which is an array
+/// of slots, such as `[[ArrayQueue<[(u64, T); 4]>; 8]; 3]` for three levels of
+/// eight slots of four items each. Each slot is a queue of items paired with
+/// their deadlines.
+///
+/// A slot at level `l` spans `S^l` ticks where `S` is the number of slots per
+/// level. Items are scheduled at the lowest level whose slots tell their
+/// deadlines apart from the current time and cascade down a level whenever the
+/// time reaches their slots, so each of them is moved at most once per level.
+/// Only items beyond the span of the top level wait there for more rotations.
+///
+/// If a cascade finds the slot of an item full, the item is moved to another
+/// slot which is reached by its deadline, or to the next slot with room if
+/// there is none. Such items can expire late but never early.
+pub struct TimerWheel<L: Array + AsRef<[<L as Array>::Item]> + AsMut<[<L as Array>::Item]>> {
+    levels: L,
+    now: u64,
+    // The time up to which all slots have been swept.
+    swept: u64,
+}
+
+impl<
+        T,
+        A: Array<Item = (u64, T)> + AsRef<[(u64, T)]> + AsMut<[(u64, T)]>,
+        W: ArrayShape<Item = ArrayQueue<A>>,
+        L: ArrayShape<Item = W>,
+    > TimerWheel<L>
+{
+    pub fn new() -> Self {
+        TimerWheel {
+            levels: L::from_fn(|_| W::from_fn(|_| ArrayQueue::new())),
+            now: 0,
+            swept: 0,
+        }
+    }
+
+    /// Schedules an item to expire after a number of ticks. Zero ticks are
+    /// treated as one.
+    ///
+    /// Fails if the slot of the item is full or if the time has reached
+    /// `u64::MAX` and no deadline is left after it.
+    pub fn schedule(&mut self, ticks: u64, x: T) -> Result<(), CapacityError<T>> {
+        let t = self.now.saturating_add(ticks.max(1));
+
+        if self.level_count() == 0 || self.slot_count() == 0 || t == self.now {
+            return Err(CapacityError::new(x));
+        }
+
+        let (l, s) = self.position(t);
+
+        self.slot_mut(l, s)
+            .push_back((t, x))
+            .map_err(|e| CapacityError::new(e.element().1))
+    }
+
+    /// Advances the wheel by a number of ticks and returns expired items.
+    /// The time saturates at `u64::MAX`.
+    ///
+    /// The time advances immediately while expired items are taken out of
+    /// their slots as the iterator is consumed. If the iterator is dropped
+    /// early, the rest of the expired items are returned by the next call.
+    pub fn advance(&mut self, ticks: u64) -> TimerWheelExpired<'_, L> {
+        self.now = self.now.saturating_add(ticks);
+
+        TimerWheelExpired {
+            wheel: self,
+            level: 0,
+            window: 0,
+            end: 0,
+            slot: None,
+            rest: 0,
+        }
+    }
+
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    pub fn clear(&mut self) {
+        for w in self.levels.as_mut() {
+            for s in w.as_mut() {
+                s.clear();
+            }
+        }
+
+        self.swept = self.now;
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels
+            .as_ref()
+            .iter()
+            .flat_map(|w| w.as_ref())
+            .map(|s| s.len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels
+            .as_ref()
+            .iter()
+            .flat_map(|w| w.as_ref())
+            .all(|s| s.is_empty())
+    }
+
+    pub fn level_count(&self) -> usize {
+        L::capacity()
+    }
+
+    /// Returns the number of slots per level.
+    pub fn slot_count(&self) -> usize {
+        W::capacity()
+    }
+
+    pub fn slot_capacity(&self) -> usize {
+        A::capacity()
+    }
+
+    fn slot<'a>(&'a self, l: usize, s: usize) -> &'a ArrayQueue<A>
+    where
+        W: 'a,
+    {
+        &self.levels.as_ref()[l].as_ref()[s]
+    }
+
+    fn slot_mut<'a>(&'a mut self, l: usize, s: usize) -> &'a mut ArrayQueue<A>
+    where
+        W: 'a,
+    {
+        &mut self.levels.as_mut()[l].as_mut()[s]
+    }
+
+    // Returns the number of ticks spanned by a slot at a level, or `None` if
+    // it exceeds the range of time.
+    fn span(&self, l: usize) -> Option<u64> {
+        (self.slot_count() as u64).checked_pow(l as u32)
+    }
+
+    // Returns the level and slot of a deadline after the current time.
+    fn position(&self, t: u64) -> (usize, usize) {
+        let n = self.level_count();
+        let l = (0..n)
+            .find(|&l| match self.span(l + 1) {
+                Some(g) => t / g == self.now / g,
+                None => true,
+            })
+            .unwrap_or(n - 1);
+
+        (
+            l,
+            (t / self.span(l).unwrap() % self.slot_count() as u64) as usize,
+        )
+    }
+
+    // Returns the next time after the current one when a slot is reached.
+    fn reached_at(&self, l: usize, s: usize) -> Option<u64> {
+        let g = self.span(l)?;
+        let n = self.slot_count() as u64;
+        let k = self.now / g;
+
+        k.checked_add((s as u64 + n - k % n - 1) % n + 1)?
+            .checked_mul(g)
+    }
+
+    // Returns the slot with room which is reached last by a deadline, or the
+    // one reached first after it if there is none.
+    fn vacancy(&self, t: u64) -> Option<(usize, usize)> {
+        let mut best: Option<(u64, usize, usize)> = None;
+
+        for l in 0..self.level_count() {
+            for s in 0..self.slot_count() {
+                if self.slot(l, s).is_full() {
+                    continue;
+                }
+
+                if let Some(u) = self.reached_at(l, s) {
+                    let better = match best {
+                        Some((v, _, _)) if u <= t => v > t || u > v,
+                        Some((v, _, _)) => v > t && u < v,
+                        None => true,
+                    };
+
+                    if better {
+                        best = Some((u, l, s));
+                    }
+                }
+            }
+        }
+
+        best.map(|(_, l, s)| (l, s))
+    }
+
+    // Moves an item taken out of a slot to where it belongs now.
+    fn reschedule(&mut self, t: u64, x: T, (l, s): (usize, usize)) {
+        let (m, r) = self.position(t);
+        let (m, r) = if self.slot(m, r).is_full() {
+            self.vacancy(t).unwrap_or((l, s))
+        } else {
+            (m, r)
+        };
+
+        self.slot_mut(m, r).push_back((t, x)).ok().unwrap();
+    }
+}
+
+impl<
+        T: Debug,
+        A: Array<Item = (u64, T)> + AsRef<[(u64, T)]> + AsMut<[(u64, T)]>,
+        W: ArrayShape<Item = ArrayQueue<A>> + Debug,
+        L: ArrayShape<Item = W>,
+    > Debug for TimerWheel<L>
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TimerWheel")
+            .field("levels", &self.levels.as_ref())
+            .field("now", &self.now)
+            .finish()
+    }
+}
+
+impl<
+        T,
+        A: Array<Item = (u64, T)> + AsRef<[(u64, T)]> + AsMut<[(u64, T)]>,
+        W: ArrayShape<Item = ArrayQueue<A>>,
+        L: ArrayShape<Item = W>,
+    > Default for TimerWheel<L>
+{
+    fn default() -> Self {
+        TimerWheel::new()
+    }
+}
+
+pub struct TimerWheelExpired<
+    'a,
+    L: 'a + Array + AsRef<[<L as Array>::Item]> + AsMut<[<L as Array>::Item]>,
+> {
+    wheel: &'a mut TimerWheel<L>,
+    // The number of levels started and the range of windows of the current
+    // level to sweep. Each window is the span of a slot at a time.
+    level: usize,
+    window: u64,
+    end: u64,
+    // The slot being swept and the number of its items not checked yet.
+    slot: Option<(usize, usize)>,
+    rest: usize,
+}
+
+impl<
+        'a,
+        T,
+        A: Array<Item = (u64, T)> + AsRef<[(u64, T)]> + AsMut<[(u64, T)]>,
+        W: ArrayShape<Item = ArrayQueue<A>>,
+        L: ArrayShape<Item = W>,
+    > Iterator for TimerWheelExpired<'a, L>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let now = self.wheel.now;
+
+            if let Some((l, s)) = self.slot {
+                while self.rest > 0 {
+                    self.rest -= 1;
+
+                    let (t, x) = self.wheel.slot_mut(l, s).pop_front().unwrap();
+
+                    if t <= now {
+                        return Some(x);
+                    }
+
+                    self.wheel.reschedule(t, x, (l, s));
+                }
+
+                self.slot = None;
+            }
+
+            if self.window < self.end {
+                self.window += 1;
+
+                let l = self.level - 1;
+                let s = (self.window % self.wheel.slot_count() as u64) as usize;
+
+                self.slot = Some((l, s));
+                self.rest = self.wheel.slot(l, s).len();
+            } else if self.level < self.wheel.level_count() {
+                // Sweeping more windows than a level has slots is redundant
+                // because every item is checked against the current time.
+                if let Some(g) = self.wheel.span(self.level) {
+                    let (i, j) = (self.wheel.swept / g, now / g);
+
+                    self.window = i;
+                    self.end = i + (j - i).min(self.wheel.slot_count() as u64);
+                }
+
+                self.level += 1;
+            } else {
+                self.wheel.swept = now;
+                return None;
+            }
+        }
+    }
+}
+
+impl<
+        'a,
+        T,
+        A: Array<Item = (u64, T)> + AsRef<[(u64, T)]> + AsMut<[(u64, T)]>,
+        W: ArrayShape<Item = ArrayQueue<A>>,
+        L: ArrayShape<Item = W>,
+    > FusedIterator for TimerWheelExpired<'a, L>
+{
+}
+
+impl<'a, L: Array + AsRef<[<L as Array>::Item]> + AsMut<[<L as Array>::Item]>> Debug
+    for TimerWheelExpired<'a, L>
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TimerWheelExpired")
+            .field("now", &self.wheel.now)
+            .field("swept", &self.wheel.swept)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Wheel = TimerWheel<[[ArrayQueue<[(u64, usize); 4]>; 8]; 2]>;
+    type SmallWheel<T> = TimerWheel<[[ArrayQueue<[(u64, T); 2]>; 4]; 2]>;
+
+    #[test]
+    fn new() {
+        TimerWheel::<[[ArrayQueue<[(u64, usize); 0]>; 0]; 0]>::new();
+        Wheel::new();
+    }
+
+    #[test]
+    fn schedule() {
+        let mut w = Wheel::new();
+
+        assert!(w.schedule(3, 1).is_ok());
+        assert!(w.schedule(1, 2).is_ok());
+        assert!(w.schedule(0, 3).is_ok());
+        assert_eq!(w.len(), 3);
+        assert_eq!(w.advance(1).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(w.advance(1).next(), None);
+        assert_eq!(w.advance(1).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(w.now(), 3);
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn schedule_into_full_slot() {
+        let mut w = Wheel::new();
+
+        for x in 0..4 {
+            assert!(w.schedule(2, x).is_ok());
+        }
+
+        assert_eq!(w.schedule(2, 4), Err(CapacityError::new(4)));
+        assert!(w.schedule(10, 4).is_ok());
+        assert_eq!(
+            TimerWheel::<[[ArrayQueue<[(u64, usize); 4]>; 0]; 1]>::new().schedule(1, 0),
+            Err(CapacityError::new(0))
+        );
+        assert_eq!(
+            TimerWheel::<[[ArrayQueue<[(u64, usize); 4]>; 8]; 0]>::new().schedule(1, 0),
+            Err(CapacityError::new(0))
+        );
+    }
+
+    #[test]
+    fn cascade() {
+        let mut w = Wheel::new();
+
+        assert!(w.schedule(20, 1).is_ok());
+        assert!(w.schedule(4, 2).is_ok());
+        assert_eq!(w.advance(19).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(w.advance(1).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn schedule_beyond_top_level() {
+        let mut w = Wheel::new();
+
+        assert!(w.schedule(100, 1).is_ok());
+        assert_eq!(w.advance(63).next(), None);
+        assert_eq!(w.advance(36).next(), None);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w.advance(1).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn cascade_into_full_slot() {
+        let mut w: SmallWheel<usize> = TimerWheel::new();
+
+        assert!(w.schedule(5, 1).is_ok());
+        assert!(w.schedule(6, 2).is_ok());
+
+        w.advance(4);
+
+        assert!(w.schedule(2, 3).is_ok());
+        assert!(w.schedule(2, 4).is_ok());
+        assert_eq!(w.advance(0).next(), None);
+        assert_eq!(w.advance(1).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(w.advance(1).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(w.advance(1).collect::<Vec<_>>(), vec![2]);
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn advance_lazily() {
+        let mut w = Wheel::new();
+
+        for t in 1..5 {
+            assert!(w.schedule(t, t as usize).is_ok());
+        }
+
+        assert_eq!(w.advance(10).next(), Some(1));
+        assert_eq!(w.now(), 10);
+        assert_eq!(w.len(), 3);
+        assert_eq!(w.advance(0).collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn keep_expired_items_of_dropped_iterator() {
+        let mut w: SmallWheel<Box<usize>> = TimerWheel::new();
+
+        assert!(w.schedule(1, Box::new(1)).is_ok());
+        assert!(w.schedule(6, Box::new(2)).is_ok());
+
+        w.advance(5);
+
+        assert_eq!(w.len(), 2);
+        assert_eq!(
+            w.advance(1).collect::<Vec<_>>(),
+            vec![Box::new(1), Box::new(2)]
+        );
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn exhaust_time() {
+        let mut w = Wheel::new();
+
+        assert_eq!(w.advance(1).next(), None);
+        assert!(w.schedule(u64::MAX, 1).is_ok());
+        assert_eq!(w.advance(u64::MAX - 2).next(), None);
+        assert_eq!(w.advance(u64::MAX).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(w.now(), u64::MAX);
+        assert_eq!(w.schedule(1, 2), Err(CapacityError::new(2)));
+    }
+
+    #[test]
+    fn compare_with_naive_implementation() {
+        type Slot = ArrayQueue<[(u64, usize); 16]>;
+
+        let mut w: TimerWheel<[[Slot; 4]; 3]> = TimerWheel::new();
+        let mut xs = vec![];
+        let mut x: u64 = 42;
+
+        for i in 0..2000 {
+            x = (x * 1103515245 + 12345) % 2147483648;
+
+            let t = (x >> 8) % 150;
+
+            if t < 100 {
+                assert!(w.schedule(t, i).is_ok());
+                xs.push((w.now() + t.max(1), i));
+            }
+
+            let mut ys = w.advance((x >> 16) % 10).collect::<Vec<_>>();
+            let mut zs = xs
+                .iter()
+                .filter(|&&(t, _)| t <= w.now())
+                .map(|&(_, i)| i)
+                .collect::<Vec<_>>();
+
+            xs.retain(|&(t, _)| t > w.now());
+            ys.sort();
+            zs.sort();
+
+            assert_eq!(ys, zs);
+            assert_eq!(w.len(), xs.len());
+        }
+    }
+}
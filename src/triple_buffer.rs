@@ -0,0 +1,221 @@
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const INDEX_MASK: u8 = 0b11;
+const FRESH: u8 = 0b100;
+
+/// A wait-free buffer which passes the latest snapshot of a value from a
+/// writer to a reader.
+///
+/// The writer and the reader each own one of the three slots and exchange
+/// theirs with the remaining one on publishing and reading respectively.
+pub struct TripleBuffer<T> {
+    slots: [UnsafeCell<T>; 3],
+    // An index of the slot owned by neither side, with a flag telling if it
+    // holds a snapshot which has not been read yet.
+    back: AtomicU8,
+    write: u8,
+    read: u8,
+}
+
+impl<T> TripleBuffer<T> {
+    pub fn new(x: T) -> Self
+    where
+        T: Clone,
+    {
+        TripleBuffer {
+            slots: [
+                UnsafeCell::new(x.clone()),
+                UnsafeCell::new(x.clone()),
+                UnsafeCell::new(x),
+            ],
+            back: AtomicU8::new(1),
+            write: 0,
+            read: 2,
+        }
+    }
+
+    pub fn split(&mut self) -> (TripleBufferWriter<'_, T>, TripleBufferReader<'_, T>) {
+        let TripleBuffer {
+            slots,
+            back,
+            write,
+            read,
+        } = self;
+
+        (
+            TripleBufferWriter {
+                slots,
+                back,
+                index: write,
+            },
+            TripleBufferReader {
+                slots,
+                back,
+                index: read,
+            },
+        )
+    }
+}
+
+impl<T: Debug> Debug for TripleBuffer<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("TripleBuffer")
+            .field(unsafe { &*self.slots[self.read as usize].get() })
+            .finish()
+    }
+}
+
+impl<T: Clone + Default> Default for TripleBuffer<T> {
+    fn default() -> Self {
+        TripleBuffer::new(T::default())
+    }
+}
+
+pub struct TripleBufferWriter<'a, T: 'a> {
+    slots: &'a [UnsafeCell<T>; 3],
+    back: &'a AtomicU8,
+    index: &'a mut u8,
+}
+
+unsafe impl<'a, T: Send> Send for TripleBufferWriter<'a, T> {}
+
+impl<'a, T> TripleBufferWriter<'a, T> {
+    /// Writes and publishes a snapshot.
+    pub fn write(&mut self, x: T) {
+        *self.input() = x;
+        self.publish();
+    }
+
+    /// Returns a slot to build a next snapshot in. Its contents are left over
+    /// from an older snapshot.
+    pub fn input(&mut self) -> &mut T {
+        unsafe { &mut *self.slots[*self.index as usize].get() }
+    }
+
+    pub fn publish(&mut self) {
+        *self.index = self.back.swap(*self.index | FRESH, Ordering::AcqRel) & INDEX_MASK;
+    }
+}
+
+impl<'a, T> Debug for TripleBufferWriter<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TripleBufferWriter").finish()
+    }
+}
+
+pub struct TripleBufferReader<'a, T: 'a> {
+    slots: &'a [UnsafeCell<T>; 3],
+    back: &'a AtomicU8,
+    index: &'a mut u8,
+}
+
+unsafe impl<'a, T: Send> Send for TripleBufferReader<'a, T> {}
+
+impl<'a, T> TripleBufferReader<'a, T> {
+    /// Returns the latest published snapshot.
+    pub fn read(&mut self) -> &T {
+        if self.is_updated() {
+            *self.index = self.back.swap(*self.index, Ordering::AcqRel) & INDEX_MASK;
+        }
+
+        unsafe { &*self.slots[*self.index as usize].get() }
+    }
+
+    /// Returns `true` if a snapshot has been published since the last read.
+    pub fn is_updated(&self) -> bool {
+        self.back.load(Ordering::Relaxed) & FRESH != 0
+    }
+}
+
+impl<'a, T> Debug for TripleBufferReader<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TripleBufferReader").finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::thread;
+
+    #[test]
+    fn new() {
+        TripleBuffer::new(0);
+        TripleBuffer::<[u8; 64]>::new([0; 64]);
+    }
+
+    #[test]
+    fn write_and_read() {
+        let mut b = TripleBuffer::new(0);
+        let (mut w, mut r) = b.split();
+
+        assert!(!r.is_updated());
+        assert_eq!(*r.read(), 0);
+
+        w.write(1);
+
+        assert!(r.is_updated());
+        assert_eq!(*r.read(), 1);
+        assert!(!r.is_updated());
+        assert_eq!(*r.read(), 1);
+
+        w.write(2);
+        w.write(3);
+
+        assert_eq!(*r.read(), 3);
+    }
+
+    #[test]
+    fn input_and_publish() {
+        let mut b = TripleBuffer::new(vec![0]);
+
+        {
+            let (mut w, mut r) = b.split();
+
+            w.input().push(1);
+
+            assert_eq!(r.read(), &vec![0]);
+
+            w.publish();
+
+            assert_eq!(r.read(), &vec![0, 1]);
+        }
+
+        let (_, mut r) = b.split();
+
+        assert_eq!(r.read(), &vec![0, 1]);
+    }
+
+    #[test]
+    fn threads() {
+        const N: usize = 10000;
+
+        let mut b = TripleBuffer::new([0; 16]);
+        let (mut w, mut r) = b.split();
+
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 1..=N {
+                    w.write([i; 16]);
+                }
+            });
+
+            s.spawn(move || {
+                let mut last = 0;
+
+                while last < N {
+                    let xs = *r.read();
+
+                    assert!(xs.iter().all(|x| *x == xs[0]));
+                    assert!(xs[0] >= last);
+
+                    last = xs[0];
+                    thread::yield_now();
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt::{self, Debug, Formatter};
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::array_stack::ArrayStack;
+
+/// A history of states with undo and redo.
+///
+/// The oldest undo step is discarded when a new state is pushed and the
+/// history is full.
+pub struct UndoHistory<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    current: Option<<A as Array>::Item>,
+    // Older states are used as a stack but need to be evicted from the bottom.
+    undo: ArrayQueue<A>,
+    redo: ArrayStack<A>,
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> UndoHistory<A> {
+    pub fn new() -> Self {
+        UndoHistory {
+            current: None,
+            undo: ArrayQueue::new(),
+            redo: ArrayStack::new(),
+        }
+    }
+
+    /// Records a new state and discards all redo steps.
+    pub fn push(&mut self, x: <A as Array>::Item) {
+        if let Some(y) = self.current.replace(x) {
+            self.undo.push_back_overwrite(y);
+        }
+
+        self.redo.clear();
+    }
+
+    /// Goes back to a previous state and returns it.
+    pub fn undo(&mut self) -> Option<&<A as Array>::Item> {
+        let x = self.undo.pop_back()?;
+
+        // The total number of undo and redo steps never exceeds the capacity.
+        if let Some(y) = self.current.replace(x) {
+            self.redo.push(y).ok().unwrap();
+        }
+
+        self.current()
+    }
+
+    /// Goes forward to a state undone before and returns it.
+    pub fn redo(&mut self) -> Option<&<A as Array>::Item> {
+        let x = self.redo.pop()?;
+
+        if let Some(y) = self.current.replace(x) {
+            self.undo.push_back(y).ok().unwrap();
+        }
+
+        self.current()
+    }
+
+    pub fn current(&self) -> Option<&<A as Array>::Item> {
+        self.current.as_ref()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.current = None;
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Returns the maximum number of undo steps.
+    pub fn capacity(&self) -> usize {
+        self.undo.capacity()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Clone for UndoHistory<A>
+where
+    <A as Array>::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        UndoHistory {
+            current: self.current.clone(),
+            undo: self.undo.clone(),
+            redo: self.redo.clone(),
+        }
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Debug for UndoHistory<A>
+where
+    <A as Array>::Item: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("UndoHistory")
+            .field("current", &self.current)
+            .field("undo", &self.undo)
+            .field("redo", &self.redo)
+            .finish()
+    }
+}
+
+impl<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> Default
+    for UndoHistory<A>
+{
+    fn default() -> Self {
+        UndoHistory::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        UndoHistory::<[usize; 0]>::new();
+        UndoHistory::<[usize; 8]>::new();
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut h: UndoHistory<[usize; 4]> = UndoHistory::new();
+
+        assert_eq!(h.undo(), None);
+        assert_eq!(h.current(), None);
+
+        h.push(1);
+        h.push(2);
+        h.push(3);
+
+        assert_eq!(h.undo(), Some(&2));
+        assert_eq!(h.undo(), Some(&1));
+        assert_eq!(h.undo(), None);
+        assert_eq!(h.current(), Some(&1));
+        assert_eq!(h.redo(), Some(&2));
+        assert_eq!(h.redo(), Some(&3));
+        assert_eq!(h.redo(), None);
+        assert_eq!(h.current(), Some(&3));
+    }
+
+    #[test]
+    fn push_discards_redo_steps() {
+        let mut h: UndoHistory<[usize; 4]> = UndoHistory::new();
+
+        h.push(1);
+        h.push(2);
+        h.undo();
+
+        assert!(h.can_redo());
+
+        h.push(3);
+
+        assert!(!h.can_redo());
+        assert_eq!(h.undo(), Some(&1));
+    }
+
+    #[test]
+    fn evict_oldest_step() {
+        let mut h: UndoHistory<[usize; 2]> = UndoHistory::new();
+
+        for x in 0..5 {
+            h.push(x);
+        }
+
+        assert_eq!(h.undo(), Some(&3));
+        assert_eq!(h.undo(), Some(&2));
+        assert!(!h.can_undo());
+        assert_eq!(h.redo(), Some(&3));
+        assert_eq!(h.redo(), Some(&4));
+        assert!(!h.can_redo());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut h: UndoHistory<[usize; 0]> = UndoHistory::new();
+
+        h.push(1);
+        h.push(2);
+
+        assert_eq!(h.undo(), None);
+        assert_eq!(h.current(), Some(&2));
+    }
+}
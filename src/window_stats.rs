@@ -0,0 +1,307 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Add, Sub};
+
+use arrayvec::Array;
+
+use super::array_queue::ArrayQueue;
+use super::sliding_window::SlidingWindow;
+
+/// Running statistics over the last N values.
+///
+/// The sum is updated incrementally, so floating point sums can accumulate
+/// rounding errors and a NaN keeps it NaN after leaving the window. The sum is
+/// recomputed from the window at least once every N pushes to recover.
+pub struct WindowStats<A: Array + AsRef<[<A as Array>::Item]> + AsMut<[<A as Array>::Item]>> {
+    window: SlidingWindow<A>,
+    sum: <A as Array>::Item,
+    // The window is split into older and newer values. The queues hold the
+    // extrema of each suffix of the older values, so the front of each is
+    // dropped whenever a value leaves the window. Values are never compared
+    // for equality, which NaN would break.
+    minimums: ArrayQueue<A>,
+    maximums: ArrayQueue<A>,
+    // The minimum and maximum of the newer values.
+    newer: Option<(<A as Array>::Item, <A as Array>::Item)>,
+}
+
+impl<T, A> WindowStats<A>
+where
+    T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>,
+    A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>,
+{
+    pub fn new() -> Self {
+        WindowStats {
+            window: SlidingWindow::new(),
+            sum: T::default(),
+            minimums: ArrayQueue::new(),
+            maximums: ArrayQueue::new(),
+            newer: None,
+        }
+    }
+
+    pub fn push(&mut self, x: T) -> Option<T> {
+        if self.capacity() == 0 {
+            return Some(x);
+        }
+
+        if self.is_full() && self.minimums.is_empty() {
+            self.split();
+        }
+
+        let y = self.window.push(x);
+
+        if let Some(y) = y {
+            self.sum = self.sum - y;
+            self.minimums.pop_front();
+            self.maximums.pop_front();
+        }
+
+        self.sum = self.sum + x;
+        self.newer = Some(match self.newer {
+            Some((min, max)) => (minimum(min, x), maximum(max, x)),
+            None => (x, x),
+        });
+
+        y
+    }
+
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    /// Returns the mean of the values in the window.
+    ///
+    /// The sum is converted with `f` rather than `Into<f64>` because integer
+    /// types like `i64`, `u64` and `usize` have no lossless conversion to
+    /// `f64`. Pass `f64::from` for types which do, or `|x| x as f64` otherwise.
+    pub fn mean<F: FnOnce(T) -> f64>(&self, f: F) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(f(self.sum) / self.len() as f64)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        match (self.minimums.first(), self.newer) {
+            (Some(&x), Some((y, _))) => Some(minimum(x, y)),
+            (x, y) => x.cloned().or(y.map(|(y, _)| y)),
+        }
+    }
+
+    pub fn max(&self) -> Option<T> {
+        match (self.maximums.first(), self.newer) {
+            (Some(&x), Some((_, y))) => Some(maximum(x, y)),
+            (x, y) => x.cloned().or(y.map(|(_, y)| y)),
+        }
+    }
+
+    pub fn window(&self) -> &SlidingWindow<A> {
+        &self.window
+    }
+
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.sum = T::default();
+        self.minimums.clear();
+        self.maximums.clear();
+        self.newer = None;
+    }
+
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+
+    // Turns all values in the window into older ones.
+    fn split(&mut self) {
+        let (xs, ys) = self.window.as_slices();
+        let mut sum = T::default();
+
+        for &x in xs.iter().chain(ys).rev() {
+            sum = sum + x;
+
+            let (min, max) = match (self.minimums.first(), self.maximums.first()) {
+                (Some(&min), Some(&max)) => (minimum(min, x), maximum(max, x)),
+                _ => (x, x),
+            };
+
+            self.minimums.push_front(min).ok().unwrap();
+            self.maximums.push_front(max).ok().unwrap();
+        }
+
+        self.sum = sum;
+        self.newer = None;
+    }
+}
+
+fn minimum<T: PartialOrd>(x: T, y: T) -> T {
+    if y < x {
+        y
+    } else {
+        x
+    }
+}
+
+fn maximum<T: PartialOrd>(x: T, y: T) -> T {
+    if y > x {
+        y
+    } else {
+        x
+    }
+}
+
+impl<T: Copy, A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>> Clone for WindowStats<A> {
+    fn clone(&self) -> Self {
+        WindowStats {
+            window: self.window.clone(),
+            sum: self.sum,
+            minimums: self.minimums.clone(),
+            maximums: self.maximums.clone(),
+            newer: self.newer,
+        }
+    }
+}
+
+impl<T, A> Debug for WindowStats<A>
+where
+    T: Copy + Debug + Default + PartialOrd + Add<Output = T> + Sub<Output = T>,
+    A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("WindowStats")
+            .field("window", &self.window)
+            .field("sum", &self.sum)
+            .field("min", &self.min())
+            .field("max", &self.max())
+            .finish()
+    }
+}
+
+impl<T, A> Default for WindowStats<A>
+where
+    T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>,
+    A: Array<Item = T> + AsRef<[T]> + AsMut<[T]>,
+{
+    fn default() -> Self {
+        WindowStats::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new() {
+        WindowStats::<[i32; 0]>::new();
+        WindowStats::<[f64; 8]>::new();
+    }
+
+    #[test]
+    fn push() {
+        let mut s: WindowStats<[i32; 3]> = WindowStats::new();
+
+        assert_eq!(s.mean(f64::from), None);
+        assert_eq!(s.min(), None);
+        assert_eq!(s.max(), None);
+
+        assert_eq!(s.push(3), None);
+        assert_eq!(s.push(1), None);
+        assert_eq!(s.push(2), None);
+        assert_eq!(s.sum(), 6);
+        assert_eq!(s.mean(f64::from), Some(2.0));
+        assert_eq!(s.min(), Some(1));
+        assert_eq!(s.max(), Some(3));
+
+        assert_eq!(s.push(2), Some(3));
+        assert_eq!(s.sum(), 5);
+        assert_eq!(s.min(), Some(1));
+        assert_eq!(s.max(), Some(2));
+
+        assert_eq!(s.push(5), Some(1));
+        assert_eq!(s.min(), Some(2));
+        assert_eq!(s.max(), Some(5));
+    }
+
+    #[test]
+    fn push_into_zero_capacity() {
+        let mut s: WindowStats<[i32; 0]> = WindowStats::new();
+
+        assert_eq!(s.push(1), Some(1));
+        assert_eq!(s.sum(), 0);
+        assert_eq!(s.max(), None);
+    }
+
+    #[test]
+    fn duplicate_extrema() {
+        let mut s: WindowStats<[i32; 2]> = WindowStats::new();
+
+        s.push(4);
+        s.push(4);
+        s.push(1);
+
+        assert_eq!(s.max(), Some(4));
+        assert_eq!(s.min(), Some(1));
+
+        s.push(1);
+
+        assert_eq!(s.max(), Some(1));
+    }
+
+    #[test]
+    fn push_nan() {
+        let mut s: WindowStats<[f64; 2]> = WindowStats::new();
+
+        for &x in &[f64::NAN, 1.0, 2.0, 3.0] {
+            s.push(x);
+        }
+
+        assert_eq!(s.min(), Some(2.0));
+        assert_eq!(s.max(), Some(3.0));
+
+        for &x in &[4.0, f64::NAN, 5.0, 6.0, 7.0] {
+            s.push(x);
+        }
+
+        assert_eq!(s.mean(|x| x), Some(6.5));
+        assert_eq!(s.min(), Some(6.0));
+        assert_eq!(s.max(), Some(7.0));
+    }
+
+    #[test]
+    fn compare_with_naive_implementation() {
+        let mut s: WindowStats<[i64; 8]> = WindowStats::new();
+        let mut xs = vec![];
+        let mut x: i64 = 42;
+
+        for _ in 0..1000 {
+            x = (x * 1103515245 + 12345) % 2147483648;
+            let y = x % 100 - 50;
+
+            s.push(y);
+            xs.push(y);
+
+            let w = &xs[xs.len().saturating_sub(8)..];
+
+            assert_eq!(s.sum(), w.iter().sum::<i64>());
+            assert_eq!(
+                s.mean(|x| x as f64),
+                Some(w.iter().sum::<i64>() as f64 / w.len() as f64)
+            );
+            assert_eq!(s.min(), w.iter().cloned().min());
+            assert_eq!(s.max(), w.iter().cloned().max());
+        }
+    }
+}